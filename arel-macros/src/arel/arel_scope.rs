@@ -0,0 +1,129 @@
+use quote::ToTokens;
+
+// #[arel]
+// impl User {
+//     #[arel(scope)]
+//     fn visible_to(q: &mut arel::manager::SelectManager<Self>, user_id: i64) {
+//         q.r#where("owner_id", user_id);
+//     }
+// }
+// =>
+// impl User {
+//     fn visible_to(q: &mut arel::manager::SelectManager<Self>, user_id: i64) { .. }
+// }
+// trait UserScopes {
+//     fn visible_to(&mut self, user_id: i64) -> &mut Self;
+// }
+// impl UserScopes for arel::manager::SelectManager<User> {
+//     fn visible_to(&mut self, user_id: i64) -> &mut Self {
+//         let _ = <User>::visible_to(self, user_id);
+//         self
+//     }
+// }
+pub(crate) fn expand_scopes(item_impl: &syn::ItemImpl) -> syn::Result<proc_macro2::TokenStream> {
+    if item_impl.trait_.is_some() || !item_impl.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(&item_impl.self_ty, "arel scopes only allow use on a plain `impl Model` block"));
+    }
+    let self_ty = &item_impl.self_ty;
+    let model_ident = match self_ty.as_ref() {
+        syn::Type::Path(type_path) => match type_path.path.segments.last() {
+            Some(segment) => &segment.ident,
+            None => return Err(syn::Error::new_spanned(self_ty, "expected a model type")),
+        },
+        _ => return Err(syn::Error::new_spanned(self_ty, "expected a model type")),
+    };
+    let scopes_ident = quote::format_ident!("{}Scopes", model_ident);
+
+    let mut new_impl = item_impl.clone();
+    let mut scopes_vis = syn::Visibility::Inherited;
+    let mut trait_methods = vec![];
+    let mut impl_methods = vec![];
+    for impl_item in new_impl.items.iter_mut() {
+        let method = match impl_item {
+            syn::ImplItem::Fn(method) => method,
+            _ => continue,
+        };
+        let metas = method.attrs.iter().map(|attr| &attr.meta).collect::<Vec<&syn::Meta>>();
+        if crate::ItemInput::get_path_value_from_metas(metas, vec!["arel"], "scope", None)?.is_none() {
+            continue;
+        }
+        method.attrs.retain(|attr| !attr.path().is_ident("arel"));
+
+        let sig = &method.sig;
+        if sig.asyncness.is_some() {
+            return Err(syn::Error::new_spanned(sig, "arel scope can not be async"));
+        }
+        let mut inputs = sig.inputs.iter();
+        match inputs.next() {
+            Some(syn::FnArg::Typed(_)) => (),
+            _ => return Err(syn::Error::new_spanned(sig, "arel scope expects the query as first argument: `fn scope(q: &mut SelectManager<Self>, ..)`")),
+        }
+        let mut arg_idents = vec![];
+        let mut args = vec![];
+        for input in inputs {
+            match input {
+                syn::FnArg::Typed(pat_type) => match pat_type.pat.as_ref() {
+                    syn::Pat::Ident(pat_ident) => {
+                        let ident = &pat_ident.ident;
+                        let r#type = replace_self(pat_type.ty.to_token_stream(), self_ty);
+                        arg_idents.push(ident.clone());
+                        args.push(quote::quote!(#ident: #r#type));
+                    }
+                    _ => return Err(syn::Error::new_spanned(pat_type, "arel scope arguments must be plain identifiers")),
+                },
+                _ => return Err(syn::Error::new_spanned(input, "arel scope expects the query as first argument")),
+            }
+        }
+        if let syn::Visibility::Inherited = scopes_vis {
+            scopes_vis = method.vis.clone();
+        }
+
+        let ident = &sig.ident;
+        let generics = replace_self(sig.generics.to_token_stream(), self_ty);
+        let where_clause = replace_self(sig.generics.where_clause.to_token_stream(), self_ty);
+        let docs = method.attrs.iter().filter(|attr| attr.path().is_ident("doc")).collect::<Vec<&syn::Attribute>>();
+        trait_methods.push(quote::quote!(
+            #(#docs)*
+            fn #ident #generics (&mut self, #(#args),*) -> &mut Self #where_clause;
+        ));
+        impl_methods.push(quote::quote!(
+            fn #ident #generics (&mut self, #(#args),*) -> &mut Self #where_clause {
+                let _ = <#self_ty>::#ident(self, #(#arg_idents),*);
+                self
+            }
+        ));
+    }
+
+    if trait_methods.is_empty() {
+        return Ok(new_impl.to_token_stream());
+    }
+    let doc = format!("Named scopes of [`{}`], chainable on `SelectManager<{}>`.", model_ident, model_ident);
+    Ok(quote::quote!(
+        #new_impl
+
+        #[doc = #doc]
+        #scopes_vis trait #scopes_ident {
+            #(#trait_methods)*
+        }
+
+        impl #scopes_ident for arel::manager::SelectManager<#self_ty> {
+            #(#impl_methods)*
+        }
+    ))
+}
+
+// `Self` means the model inside the impl block, but the SelectManager inside the generated trait impl
+fn replace_self(tokens: proc_macro2::TokenStream, self_ty: &syn::Type) -> proc_macro2::TokenStream {
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            proc_macro2::TokenTree::Ident(ident) if ident == "Self" => self_ty.to_token_stream(),
+            proc_macro2::TokenTree::Group(group) => {
+                let mut new_group = proc_macro2::Group::new(group.delimiter(), replace_self(group.stream(), self_ty));
+                new_group.set_span(group.span());
+                proc_macro2::TokenTree::Group(new_group).into()
+            }
+            token => token.into(),
+        })
+        .collect()
+}
@@ -92,6 +92,71 @@ pub(crate) fn impl_primary_values(input: &crate::ItemInput) -> syn::Result<proc_
     ))
 }
 
+// fn attribute_value(&self, name: &str) -> Option<arel::Value>;
+pub(crate) fn impl_attribute_value(input: &crate::ItemInput) -> syn::Result<proc_macro2::TokenStream> {
+    let mut match_arms = vec![];
+
+    let fields = input.struct_fields()?;
+    for field in fields.iter() {
+        let ident = &field.ident;
+        let field_name = {
+            if let Some((rename, _)) = crate::ItemInput::get_field_path_value(field, vec!["arel"], "rename", None)? {
+                rename
+            } else {
+                match ident {
+                    Some(ident) => ident.to_string().trim_start_matches("r#").to_string(),
+                    _ => return Err(syn::Error::new_spanned(field, "Field name can not Blank!")),
+                }
+            }
+        };
+        match_arms.push(quote::quote!(
+            #field_name => self.#ident.value().map(|v| v.clone().into()),
+        ));
+    }
+
+    Ok(quote::quote!(
+        fn attribute_value(&self, name: &str) -> Option<arel::Value> {
+            match name {
+                #(#match_arms)*
+                _ => None,
+            }
+        }
+    ))
+}
+
+// fn changed_values(&self) -> Vec<(&'static str, arel::Value)>;
+pub(crate) fn impl_changed_values(input: &crate::ItemInput) -> syn::Result<proc_macro2::TokenStream> {
+    let fields = input.struct_fields()?;
+
+    let mut changed_fields_clause = vec![];
+    for field in fields.iter() {
+        let ident = &field.ident;
+        let field_name = {
+            if let Some((rename, _)) = crate::ItemInput::get_field_path_value(field, vec!["arel"], "rename", None)? {
+                rename
+            } else {
+                match ident {
+                    Some(ident) => ident.to_string().trim_start_matches("r#").to_string(),
+                    _ => return Err(syn::Error::new_spanned(field, "Field name can not Blank!")),
+                }
+            }
+        };
+        changed_fields_clause.push(quote::quote!(
+            if let arel::ActiveValue::Changed(nv, _) = &self.#ident {
+                changed_values.push((#field_name, nv.into()));
+            }
+        ));
+    }
+
+    Ok(quote::quote!(
+        fn changed_values(&self) -> Vec<(&'static str, arel::Value)> {
+            let mut changed_values = vec![];
+            #(#changed_fields_clause)*
+            changed_values
+        }
+    ))
+}
+
 // fn assign(&mut self, other: &Self) -> &mut Self;
 pub(crate) fn impl_assign(input: &crate::ItemInput) -> syn::Result<proc_macro2::TokenStream> {
     let fields = input.struct_fields()?;
@@ -145,7 +210,7 @@ pub(crate) fn impl_is_dirty(input: &crate::ItemInput) -> syn::Result<proc_macro2
     ))
 }
 
-// async fn insert_with_exec<'a, E>(&mut self, executor: E) -> arel::Result<()> where E: arel::sqlx::Executor<'a, Database = crate::db::Database>;
+// async fn insert_with_exec<'a, E>(&mut self, executor: E) -> arel::Result<()> where E: arel::db::WriteExecutor<'a>;
 pub(crate) fn impl_insert_with_exec(input: &crate::ItemInput) -> syn::Result<proc_macro2::TokenStream> {
     let fields = input.struct_fields()?;
 
@@ -197,14 +262,15 @@ pub(crate) fn impl_insert_with_exec(input: &crate::ItemInput) -> syn::Result<pro
     Ok(quote::quote!(
         async fn insert_with_exec<'a, E>(&mut self, executor: E) -> arel::Result<()>
         where
-            E: arel::sqlx::Executor<'a, Database = arel::db::Database>,
+            E: arel::db::WriteExecutor<'a>,
         {
             let mut insert_fields: Vec<&'static str> = vec![];
             let mut insert_values: Vec<arel::Value> = vec![];
             #insert_init_clause
 
+            let written = insert_fields.iter().cloned().zip(insert_values.iter().cloned()).collect();
             if let Some(insert_sql) = arel::statements::insert::Insert::<Self>::new(insert_fields, insert_values).to_sql()? {
-                *self = insert_sql.fetch_one_as_with_exec(executor).await?;
+                *self = insert_sql.fetch_written_as_with_exec(written, vec![], executor).await?;
                 Ok(())
                 // match insert_sql.fetch_one_with_exec(executor).await {
                 //     Ok(val) => {
@@ -222,7 +288,7 @@ pub(crate) fn impl_insert_with_exec(input: &crate::ItemInput) -> syn::Result<pro
     ))
 }
 
-// async fn update_with_exec<'a, E>(&mut self, executor: E) -> arel::Result<()> where E: arel::sqlx::Executor<'a, Database = arel::db::Database>;
+// async fn update_with_exec<'a, E>(&mut self, executor: E) -> arel::Result<()> where E: arel::db::WriteExecutor<'a>;
 pub(crate) fn impl_update_with_exec(input: &crate::ItemInput) -> syn::Result<proc_macro2::TokenStream> {
     let fields = input.struct_fields()?;
 
@@ -266,14 +332,15 @@ pub(crate) fn impl_update_with_exec(input: &crate::ItemInput) -> syn::Result<pro
     Ok(quote::quote!(
         async fn update_with_exec<'a, E>(&mut self, executor: E) -> arel::Result<()>
         where
-            E: arel::sqlx::Executor<'a, Database = arel::db::Database>,
+            E: arel::db::WriteExecutor<'a>,
         {
             let mut update_fields: Vec<&'static str> = vec![];
             let mut update_values: Vec<arel::Value> = vec![];
             #update_init_clause
 
+            let written = update_fields.iter().cloned().zip(update_values.iter().cloned()).collect();
             if let Some(update_sql) =arel::statements::update::Update::<Self>::new(update_fields, update_values, Self::primary_keys().clone(), self.primary_values().clone()).to_sql()? {
-                *self = update_sql.fetch_one_as_with_exec(executor).await?;
+                *self = update_sql.fetch_written_as_with_exec(written, self.primary_values(), executor).await?;
                 Ok(())
                 // match update_sql.exec(executor).await {
                 //     Ok(val) => {
@@ -293,7 +360,7 @@ pub(crate) fn impl_update_with_exec(input: &crate::ItemInput) -> syn::Result<pro
     ))
 }
 
-// async fn increment_with_exec<'a, K: Send + ToString, E>(&mut self, key: K, step: i32, executor: E) -> arel::Result<()> where E: sqlx::Executor<'a, Database = arel::db::Database>
+// async fn increment_with_exec<'a, K: Send + ToString, E>(&mut self, key: K, step: i32, executor: E) -> arel::Result<()> where E: arel::db::WriteExecutor<'a>
 pub(crate) fn impl_increment_with_exec(input: &crate::ItemInput) -> syn::Result<proc_macro2::TokenStream> {
     let fields = input.struct_fields()?;
     let mut update_init_clause = proc_macro2::TokenStream::new();
@@ -331,13 +398,13 @@ pub(crate) fn impl_increment_with_exec(input: &crate::ItemInput) -> syn::Result<
     Ok(quote::quote!(
         async fn increment_with_exec<'a, K: Send + ToString, E>(&mut self, key: K, step: i32, executor: E) -> arel::Result<()>
         where
-            E: arel::sqlx::Executor<'a, Database = arel::db::Database>,
+            E: arel::db::WriteExecutor<'a>,
         {
             let mut update_field = key.to_string().trim_start_matches("r#").to_string();
             #update_init_clause
 
             if let Some(increment_sql) = arel::statements::increment::Increment::<Self>::new(update_field, step, Self::primary_keys().clone(), self.primary_values().clone()).to_sql()? {
-                *self = increment_sql.fetch_one_as_with_exec(executor).await?;
+                *self = increment_sql.fetch_written_as_with_exec(vec![], self.primary_values(), executor).await?;
                 Ok(())
             } else {
                 Err(arel::Error::Message("sql error".to_string()))
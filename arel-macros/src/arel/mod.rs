@@ -1,3 +1,4 @@
+mod arel_scope;
 mod arel_trait;
 
 use proc_macro::TokenStream;
@@ -25,19 +26,24 @@ pub fn create_arel(args: TokenStream, input: TokenStream) -> TokenStream {
 }
 
 fn do_expand(input: &crate::ItemInput) -> syn::Result<proc_macro2::TokenStream> {
+    if let syn::Item::Impl(item_impl) = &input.input {
+        return arel_scope::expand_scopes(item_impl);
+    }
     let arel_trail = do_expand_arel(input)?;
     let model_sqlx_from_row = do_expand_model_sqlx_from_row(input)?;
+    let model_columns = do_expand_model_columns(input)?;
 
     Ok(quote::quote!(
         #arel_trail
         #model_sqlx_from_row
+        #model_columns
     ))
 }
 
 fn do_expand_arel(input: &crate::ItemInput) -> syn::Result<proc_macro2::TokenStream> {
     match &input.input {
         syn::Item::Struct(_) => (),
-        _ => return Err(syn::Error::new_spanned(&input.input, "arel only allow use on struct type")),
+        _ => return Err(syn::Error::new_spanned(&input.input, "arel only allow use on struct type or impl block")),
     }
 
     let struct_ident = input.ident()?;
@@ -58,6 +64,8 @@ fn do_expand_arel(input: &crate::ItemInput) -> syn::Result<proc_macro2::TokenStr
     let arel_trait_impl_table_name = arel_trait::impl_table_name(input)?;
     let arel_trait_impl_primary_keys = arel_trait::impl_primary_keys(input)?;
     let arel_trait_impl_primary_values = arel_trait::impl_primary_values(input)?;
+    let arel_trait_impl_attribute_value = arel_trait::impl_attribute_value(input)?;
+    let arel_trait_impl_changed_values = arel_trait::impl_changed_values(input)?;
     let arel_trait_impl_assign = arel_trait::impl_assign(input)?;
     let arel_trait_impl_is_dirty = arel_trait::impl_is_dirty(input)?;
     let arel_trait_impl_insert_with_exec = arel_trait::impl_insert_with_exec(input)?;
@@ -95,15 +103,19 @@ fn do_expand_arel(input: &crate::ItemInput) -> syn::Result<proc_macro2::TokenStr
             #arel_trait_impl_primary_keys
             // fn primary_values(&self) -> Vec<arel::Value>;
             #arel_trait_impl_primary_values
+            // fn attribute_value(&self, name: &str) -> Option<arel::Value>;
+            #arel_trait_impl_attribute_value
+            // fn changed_values(&self) -> Vec<(&'static str, arel::Value)>;
+            #arel_trait_impl_changed_values
             // fn assign(&mut self, other: &Self) -> &mut Self;
             #arel_trait_impl_assign
             // fn is_dirty(&self) -> bool;
             #arel_trait_impl_is_dirty
-            // async fn insert_with_exec<'a, E>(&mut self, executor: E) -> arel::Result<()> where E: arel::sqlx::Executor<'a, Database = arel::db::Database>;
+            // async fn insert_with_exec<'a, E>(&mut self, executor: E) -> arel::Result<()> where E: arel::db::WriteExecutor<'a>;
             #arel_trait_impl_insert_with_exec
-            // async fn update_with_exec<'a, E>(&mut self, executor: E) -> arel::Result<()> where E: arel::sqlx::Executor<'a, Database = arel::db::Database>;
+            // async fn update_with_exec<'a, E>(&mut self, executor: E) -> arel::Result<()> where E: arel::db::WriteExecutor<'a>;
             #arel_trait_impl_update_with_exec
-            // async fn increment_with_exec<'a, K: Send + ToString, E>(&mut self, key: K, step: i32, executor: E) -> arel::Result<()> where E: arel::sqlx::Executor<'a, Database = arel::db::Database>
+            // async fn increment_with_exec<'a, K: Send + ToString, E>(&mut self, key: K, step: i32, executor: E) -> arel::Result<()> where E: arel::db::WriteExecutor<'a>
             #arel_trait_impl_increment_with_exec
             // async fn destroy_with_exec<'a, E>(&mut self, executor: E) -> arel::Result<()> where E: sqlx::Executor<'a, Database = arel::db::Database>;
             #arel_trait_impl_destroy_with_exec
@@ -169,6 +181,72 @@ fn do_expand_model_sqlx_from_row(input: &crate::ItemInput) -> syn::Result<proc_m
     ))
 }
 
+// #[derive(Clone, Copy, Debug)]
+// pub struct UserColumns {
+//     pub id: arel::Column<User, i32>,
+//     pub r#type: arel::Column<User, Type>,
+// }
+// impl User {
+//     pub fn columns() -> UserColumns {
+//         UserColumns { id: arel::Column::new("id"), r#type: arel::Column::new("type") }
+//     }
+// }
+fn do_expand_model_columns(input: &crate::ItemInput) -> syn::Result<proc_macro2::TokenStream> {
+    let struct_ident = input.ident()?;
+    let columns_ident = quote::format_ident!("{}Columns", struct_ident);
+    let fields = input.struct_fields()?;
+
+    let generics = input.generics()?;
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+
+    let mut column_fields = vec![];
+    let mut column_inits = vec![];
+    for field in fields.iter() {
+        let ident = &field.ident;
+        let r#type = &field.ty;
+        let field_name = {
+            if let Some((rename, _)) = crate::ItemInput::get_field_path_value(field, vec!["arel"], "rename", None)? {
+                rename
+            } else {
+                match ident {
+                    Some(ident) => ident.to_string().trim_start_matches("r#").to_string(),
+                    _ => return Err(syn::Error::new_spanned(field, "Field name can not Blank!")),
+                }
+            }
+        };
+        // public whatever the model field is, queries are written outside the model's module
+        column_fields.push(quote::quote!(
+            pub #ident: arel::Column<#struct_ident #type_generics, #r#type>
+        ));
+        column_inits.push(quote::quote!(
+            #ident: arel::Column::new(#field_name)
+        ));
+    }
+
+    let vis = input.vis()?;
+    let doc = format!("Typed column handles of [`{}`].", struct_ident);
+    Ok(quote::quote!(
+        #[doc = #doc]
+        #[derive(Clone, Copy, Debug)]
+        #vis struct #columns_ident #generics #where_clause {
+            #(#column_fields),*
+        }
+
+        impl #impl_generics #struct_ident #type_generics #where_clause {
+            /// Typed column handles, e.g. `User::columns().name`.
+            pub fn columns() -> #columns_ident #type_generics {
+                #columns_ident {
+                    #(#column_inits),*
+                }
+            }
+            /// Shorthand for `columns()`: `User::col().age`.
+            pub fn col() -> #columns_ident #type_generics {
+                Self::columns()
+            }
+        }
+    ))
+}
+
 fn new_field_type(field: &syn::Field) -> Option<syn::Type> {
     let r#type = &field.ty;
     let type_str: String = quote::quote!(#r#type).to_string().split_whitespace().collect();
@@ -0,0 +1,238 @@
+/// How an insert turns into an upsert.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UpsertStyle {
+    /// `ON CONFLICT (..) DO UPDATE SET .. | DO NOTHING`, the row that failed to insert is `excluded`
+    OnConflict,
+    /// `ON DUPLICATE KEY UPDATE ..`, the row that failed to insert is `VALUES(..)`
+    OnDuplicateKey,
+}
+
+/// How `Operator::NullSafeEq` / `Operator::NullSafeNe` render.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NullSafeStyle {
+    /// `IS NOT DISTINCT FROM` | `IS DISTINCT FROM`
+    DistinctFrom,
+    /// `<=>` | `NOT (.. <=> ..)`
+    Spaceship,
+    /// `IS` | `IS NOT`
+    Is,
+}
+
+/// The SQL differences between backends the statements render around.
+///
+/// Statements render for the enabled backend feature only, see [`compiled`].
+pub trait Dialect: std::fmt::Debug + Send + Sync {
+    /// [`sqlx::Database::NAME`] of the backend.
+    fn name(&self) -> &'static str;
+    fn quote_char(&self) -> char;
+    /// `INSERT | UPDATE .. RETURNING *`
+    fn supports_returning(&self) -> bool;
+    fn upsert_style(&self) -> UpsertStyle;
+    /// `ON CONFLICT ON CONSTRAINT "name"`
+    fn supports_conflict_constraint(&self) -> bool;
+    /// `FOR UPDATE | FOR SHARE ..`
+    fn supports_row_locks(&self) -> bool;
+    /// `FOR NO KEY UPDATE`, `FOR UPDATE` is taken otherwise.
+    fn supports_no_key_update(&self) -> bool;
+    /// `DEFAULT` inside a `VALUES` list.
+    fn supports_default_in_values(&self) -> bool;
+    /// `INSERT INTO "table" DEFAULT VALUES`, `INSERT INTO "table" () VALUES ()` otherwise.
+    fn supports_default_values(&self) -> bool;
+    /// `NULLS FIRST | NULLS LAST`, emulated with `IS NULL` otherwise.
+    fn supports_nulls_ordering(&self) -> bool;
+    /// Row value comparisons: `("a", "b") > (?, ?)`.
+    fn supports_row_values(&self) -> bool;
+    /// Compound members as `(SELECT ..)`, `SELECT * FROM (SELECT ..)` otherwise.
+    fn supports_parenthesized_compound(&self) -> bool;
+    /// `ILIKE`, emulated with `LOWER(..) LIKE LOWER(..)` otherwise.
+    fn supports_ilike(&self) -> bool;
+    /// Appended to `LIKE ?` so `\` escapes `%` and `_`.
+    fn like_escape(&self) -> &'static str;
+    fn null_safe_style(&self) -> NullSafeStyle;
+    /// Most bind parameters one statement may carry.
+    fn max_bind_params(&self) -> usize;
+    /// The `LIMIT` written before an `OFFSET` without one, `None` when `OFFSET` stands alone.
+    fn unbounded_limit(&self) -> Option<&'static str>;
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sqlite;
+
+impl Dialect for Sqlite {
+    fn name(&self) -> &'static str {
+        "SQLite"
+    }
+    fn quote_char(&self) -> char {
+        '"'
+    }
+    fn supports_returning(&self) -> bool {
+        true
+    }
+    fn upsert_style(&self) -> UpsertStyle {
+        UpsertStyle::OnConflict
+    }
+    fn supports_conflict_constraint(&self) -> bool {
+        false
+    }
+    fn supports_row_locks(&self) -> bool {
+        false
+    }
+    fn supports_no_key_update(&self) -> bool {
+        false
+    }
+    fn supports_default_in_values(&self) -> bool {
+        false
+    }
+    fn supports_default_values(&self) -> bool {
+        true
+    }
+    fn supports_nulls_ordering(&self) -> bool {
+        false
+    }
+    fn supports_row_values(&self) -> bool {
+        false
+    }
+    fn supports_parenthesized_compound(&self) -> bool {
+        false
+    }
+    fn supports_ilike(&self) -> bool {
+        false
+    }
+    // sqlite has no default LIKE escape character
+    fn like_escape(&self) -> &'static str {
+        r#" ESCAPE '\'"#
+    }
+    fn null_safe_style(&self) -> NullSafeStyle {
+        NullSafeStyle::Is
+    }
+    fn max_bind_params(&self) -> usize {
+        32766
+    }
+    fn unbounded_limit(&self) -> Option<&'static str> {
+        Some("-1")
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MySql;
+
+impl Dialect for MySql {
+    fn name(&self) -> &'static str {
+        "MySQL"
+    }
+    // double quotes are string literals unless ANSI_QUOTES is on
+    fn quote_char(&self) -> char {
+        '`'
+    }
+    fn supports_returning(&self) -> bool {
+        false
+    }
+    fn upsert_style(&self) -> UpsertStyle {
+        UpsertStyle::OnDuplicateKey
+    }
+    fn supports_conflict_constraint(&self) -> bool {
+        false
+    }
+    fn supports_row_locks(&self) -> bool {
+        true
+    }
+    fn supports_no_key_update(&self) -> bool {
+        false
+    }
+    fn supports_default_in_values(&self) -> bool {
+        true
+    }
+    fn supports_default_values(&self) -> bool {
+        false
+    }
+    fn supports_nulls_ordering(&self) -> bool {
+        false
+    }
+    fn supports_row_values(&self) -> bool {
+        true
+    }
+    fn supports_parenthesized_compound(&self) -> bool {
+        true
+    }
+    fn supports_ilike(&self) -> bool {
+        false
+    }
+    fn like_escape(&self) -> &'static str {
+        ""
+    }
+    fn null_safe_style(&self) -> NullSafeStyle {
+        NullSafeStyle::Spaceship
+    }
+    fn max_bind_params(&self) -> usize {
+        65535
+    }
+    fn unbounded_limit(&self) -> Option<&'static str> {
+        Some("18446744073709551615")
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Postgres;
+
+impl Dialect for Postgres {
+    fn name(&self) -> &'static str {
+        "PostgreSQL"
+    }
+    fn quote_char(&self) -> char {
+        '"'
+    }
+    fn supports_returning(&self) -> bool {
+        true
+    }
+    fn upsert_style(&self) -> UpsertStyle {
+        UpsertStyle::OnConflict
+    }
+    fn supports_conflict_constraint(&self) -> bool {
+        true
+    }
+    fn supports_row_locks(&self) -> bool {
+        true
+    }
+    fn supports_no_key_update(&self) -> bool {
+        true
+    }
+    fn supports_default_in_values(&self) -> bool {
+        true
+    }
+    fn supports_default_values(&self) -> bool {
+        true
+    }
+    fn supports_nulls_ordering(&self) -> bool {
+        true
+    }
+    fn supports_row_values(&self) -> bool {
+        true
+    }
+    fn supports_parenthesized_compound(&self) -> bool {
+        true
+    }
+    fn supports_ilike(&self) -> bool {
+        true
+    }
+    fn like_escape(&self) -> &'static str {
+        ""
+    }
+    fn null_safe_style(&self) -> NullSafeStyle {
+        NullSafeStyle::DistinctFrom
+    }
+    fn max_bind_params(&self) -> usize {
+        65535
+    }
+    fn unbounded_limit(&self) -> Option<&'static str> {
+        None
+    }
+}
+
+/// The dialect of the enabled backend feature.
+pub fn compiled() -> &'static dyn Dialect {
+    match <crate::db::Database as sqlx::Database>::NAME {
+        "MySQL" => &MySql,
+        "PostgreSQL" => &Postgres,
+        _ => &Sqlite,
+    }
+}
@@ -37,8 +37,24 @@ pub type DatabasePoolOptions = sqlx::postgres::PgPoolOptions;
 #[cfg(feature = "postgres")]
 pub type DatabaseQueryResult = sqlx::postgres::PgQueryResult;
 
+/// The executor of the `*_with_exec` writes that read the written row back: any [`sqlx::Executor`], or on mysql,
+/// which reads it with a second statement over the same connection, a [`sqlx::Acquire`] (pool, connection or transaction).
+#[cfg(not(feature = "mysql"))]
+pub trait WriteExecutor<'a>: sqlx::Executor<'a, Database = Database> {}
+#[cfg(not(feature = "mysql"))]
+impl<'a, E: sqlx::Executor<'a, Database = Database>> WriteExecutor<'a> for E {}
+/// The executor of the `*_with_exec` writes that read the written row back: any [`sqlx::Executor`], or on mysql,
+/// which reads it with a second statement over the same connection, a [`sqlx::Acquire`] (pool, connection or transaction).
+#[cfg(feature = "mysql")]
+pub trait WriteExecutor<'a>: sqlx::Acquire<'a, Database = Database> + Send {}
+#[cfg(feature = "mysql")]
+impl<'a, A: sqlx::Acquire<'a, Database = Database> + Send> WriteExecutor<'a> for A {}
+
+pub mod dialect;
 pub mod visitor;
 
+pub use dialect::Dialect;
+
 pub fn get_pool() -> crate::Result<&'static sqlx::Pool<Database>> {
     Ok(visitor::get()?.pool())
 }
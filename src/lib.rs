@@ -27,9 +27,14 @@ pub use sqlx;
 
 pub use bytes::Bytes;
 pub use error::Error;
-pub use manager::SelectManager;
+pub use manager::{CompoundManager, SelectManager};
 pub use sql::Sql;
-pub use statements::{join::JoinConst, order::SortConst};
+pub use statements::{
+    column::Column,
+    expr::Expr,
+    join::JoinConst,
+    order::{NullsConst, SortConst},
+};
 pub use value::{
     active_value::{ActiveValue, Set, SetChanged, SetNotSet, SetUnchanged},
     sub_value, Value,
@@ -0,0 +1,231 @@
+use super::SelectManager;
+use crate::prelude::*;
+use crate::sql::quote_ident;
+use crate::statements::column::ColumnName;
+use std::marker::PhantomData;
+
+pub enum CompoundConst {
+    Union,
+    UnionAll,
+    Intersect,
+    Except,
+}
+impl std::fmt::Display for CompoundConst {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            CompoundConst::Union => write!(f, "UNION"),
+            CompoundConst::UnionAll => write!(f, "UNION ALL"),
+            CompoundConst::Intersect => write!(f, "INTERSECT"),
+            CompoundConst::Except => write!(f, "EXCEPT"),
+        }
+    }
+}
+
+/// Queries combined by `UNION` | `UNION ALL` | `INTERSECT` | `EXCEPT`, created by [`SelectManager::union`] and friends.
+///
+/// Every query keeps its own binds, the outer `ORDER BY` refers to result columns by name.
+#[derive(Debug)]
+pub struct CompoundManager<M: crate::Arel> {
+    sqls: Vec<crate::Sql>,
+    error: Option<String>,
+    order: Option<crate::statements::order::Order<M>>,
+    limit: Option<crate::statements::limit::Limit>,
+    offset: Option<crate::statements::offset::Offset>,
+    _marker: PhantomData<M>,
+}
+
+impl<M: Arel> Clone for CompoundManager<M> {
+    fn clone(&self) -> Self {
+        Self {
+            sqls: self.sqls.clone(),
+            error: self.error.clone(),
+            order: self.order.clone(),
+            limit: self.limit.clone(),
+            offset: self.offset.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<M: Arel> CompoundManager<M> {
+    pub fn new(query: &SelectManager<M>) -> Self {
+        let mut compound_manager = Self {
+            sqls: vec![],
+            error: None,
+            order: None,
+            limit: None,
+            offset: None,
+            _marker: PhantomData,
+        };
+        compound_manager.push_query(None, query);
+        compound_manager
+    }
+    fn push_query(&mut self, compound_type: Option<CompoundConst>, query: &SelectManager<M>) -> &mut Self {
+        match query.to_sql() {
+            Ok(sql) => {
+                let mut compound_sql = crate::Sql::default();
+                if let Some(compound_type) = compound_type {
+                    compound_sql.push_str(format!(" {} ", compound_type));
+                }
+                if !crate::db::dialect::compiled().supports_parenthesized_compound() {
+                    compound_sql.push_str("SELECT * FROM (").push_sql(sql).push_str(")");
+                } else {
+                    compound_sql.push_str("(").push_sql(sql).push_str(")");
+                }
+                self.sqls.push(compound_sql);
+            }
+            Err(err) => {
+                self.error.get_or_insert(err.to_string());
+            }
+        }
+        self
+    }
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// use arel::manager::CompoundManager;
+    /// #[arel]
+    /// struct User {}
+    /// impl Arel for User {}
+    /// let mut compound_manager = CompoundManager::new(User::query().r#where("type", "ADMIN"));
+    /// compound_manager.union_all(User::query().where_gt("age", 18)).order_desc("id").limit(10);
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(
+    ///     compound_manager.to_sql().unwrap().to_sql_string().unwrap(),
+    ///     r#"SELECT * FROM (SELECT "user".* FROM "user" WHERE "user"."type" = ?) UNION ALL SELECT * FROM (SELECT "user".* FROM "user" WHERE "user"."age" > ?) ORDER BY "id" DESC LIMIT 10"#
+    /// );
+    /// #[cfg(feature = "postgres")]
+    /// assert_eq!(
+    ///     compound_manager.to_sql().unwrap().to_sql_string().unwrap(),
+    ///     r#"(SELECT "user".* FROM "user" WHERE "user"."type" = $1) UNION ALL (SELECT "user".* FROM "user" WHERE "user"."age" > $2) ORDER BY "id" DESC LIMIT 10"#
+    /// );
+    /// ```
+    pub fn union(&mut self, query: &SelectManager<M>) -> &mut Self {
+        self.push_query(Some(CompoundConst::Union), query)
+    }
+    pub fn union_all(&mut self, query: &SelectManager<M>) -> &mut Self {
+        self.push_query(Some(CompoundConst::UnionAll), query)
+    }
+    pub fn intersect(&mut self, query: &SelectManager<M>) -> &mut Self {
+        self.push_query(Some(CompoundConst::Intersect), query)
+    }
+    pub fn except(&mut self, query: &SelectManager<M>) -> &mut Self {
+        self.push_query(Some(CompoundConst::Except), query)
+    }
+    pub fn order<T: ColumnName<M>>(&mut self, column: T, sort_type: crate::SortConst) -> &mut Self {
+        self.order_sql(format!("{} {}", quote_ident(column.column_name()), sort_type))
+    }
+    pub fn order_asc<T: ColumnName<M>>(&mut self, column: T) -> &mut Self {
+        self.order(column, crate::SortConst::Asc)
+    }
+    pub fn order_desc<T: ColumnName<M>>(&mut self, column: T) -> &mut Self {
+        self.order(column, crate::SortConst::Desc)
+    }
+    pub fn order_sql<S: Into<crate::Sql>>(&mut self, sql: S) -> &mut Self {
+        self.order.get_or_insert_with(Default::default).append_sql(sql);
+        self
+    }
+    pub fn limit(&mut self, num: usize) -> &mut Self {
+        self.limit = Some(crate::statements::limit::Limit::new(num));
+        self
+    }
+    pub fn offset(&mut self, num: usize) -> &mut Self {
+        self.offset = Some(crate::statements::offset::Offset::new(num));
+        self
+    }
+    pub fn paginate(&mut self, page: usize, page_size: usize) -> &mut Self {
+        let offset = (std::cmp::max(page, 1) - 1) * page_size;
+        self.limit(page_size);
+        self.offset(offset)
+    }
+    pub fn to_sql(&self) -> crate::Result<crate::Sql> {
+        if let Some(message) = &self.error {
+            return Err(crate::Error::Message(message.clone()));
+        }
+        let mut final_sql = crate::Sql::new("");
+        for sql in self.sqls.iter() {
+            final_sql.push_sql(sql.clone());
+        }
+
+        if let Some(order) = &self.order {
+            if let Some(sql) = order.to_sql()? {
+                final_sql.push_str(" ").push_sql(sql);
+            }
+        }
+
+        if let Some(limit) = &self.limit {
+            if let Some(sql) = limit.to_sql()? {
+                final_sql.push_str(" ").push_sql(sql);
+            }
+        }
+
+        if let Some(offset) = &self.offset {
+            if let Some(sql) = offset.to_sql()? {
+                final_sql.push_str(" ").push_sql(sql);
+            }
+        }
+
+        Ok(final_sql)
+    }
+}
+
+impl<M: Arel> CompoundManager<M>
+where
+    for<'b> M: Send + Unpin + sqlx::FromRow<'b, crate::db::DatabaseRow>,
+{
+    pub fn to_query_builder<'a>(&self) -> crate::Result<crate::sql::QueryBuilder<'a>> {
+        self.to_sql()?.try_into()
+    }
+    pub(crate) async fn fetch_one_as_with_exec<'a, T, E>(&self, executor: E) -> crate::Result<T>
+    where
+        for<'b> T: Send + Unpin + sqlx::FromRow<'b, crate::db::DatabaseRow>,
+        E: sqlx::Executor<'a, Database = crate::db::Database>,
+    {
+        let ret: T = self.to_sql()?.fetch_one_as_with_exec(executor).await?;
+        Ok(ret)
+    }
+    pub(crate) async fn fetch_one_optional_as_with_exec<'a, T, E>(&self, executor: E) -> crate::Result<Option<T>>
+    where
+        for<'b> T: Send + Unpin + sqlx::FromRow<'b, crate::db::DatabaseRow>,
+        E: sqlx::Executor<'a, Database = crate::db::Database>,
+    {
+        let ret: Option<T> = self.to_sql()?.fetch_one_optional_as_with_exec(executor).await?;
+        Ok(ret)
+    }
+    pub async fn fetch_one_as<T>(&self) -> crate::Result<T>
+    where
+        for<'b> T: Send + Unpin + sqlx::FromRow<'b, crate::db::DatabaseRow>,
+    {
+        self.fetch_one_as_with_exec(M::pool()?).await
+    }
+    pub async fn fetch_one_optional_as<T>(&self) -> crate::Result<Option<T>>
+    where
+        for<'b> T: Send + Unpin + sqlx::FromRow<'b, crate::db::DatabaseRow>,
+    {
+        self.fetch_one_optional_as_with_exec(M::pool()?).await
+    }
+    pub async fn fetch_one(&self) -> crate::Result<M> {
+        self.fetch_one_as().await
+    }
+    pub async fn fetch_one_optional(&self) -> crate::Result<Option<M>> {
+        self.fetch_one_optional_as().await
+    }
+    pub(crate) async fn fetch_all_with_exec<'a, T, E>(&self, executor: E) -> crate::Result<Vec<T>>
+    where
+        for<'b> T: Send + Unpin + sqlx::FromRow<'b, crate::db::DatabaseRow>,
+        E: sqlx::Executor<'a, Database = crate::db::Database>,
+    {
+        let array: Vec<T> = self.to_sql()?.fetch_all_as_with_exec(executor).await?;
+        Ok(array)
+    }
+    pub async fn fetch_all_as<T>(&self) -> crate::Result<Vec<T>>
+    where
+        for<'b> T: Send + Unpin + sqlx::FromRow<'b, crate::db::DatabaseRow>,
+    {
+        self.fetch_all_with_exec(M::pool()?).await
+    }
+    pub async fn fetch_all(&self) -> crate::Result<Vec<M>> {
+        self.fetch_all_as().await
+    }
+}
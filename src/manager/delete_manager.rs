@@ -0,0 +1,83 @@
+use super::SelectManager;
+use crate::prelude::*;
+use crate::sql::quote_ident;
+use std::marker::PhantomData;
+
+/// Set-based `DELETE` of the rows a [`SelectManager`] matches, created by [`SelectManager::delete_manager`].
+///
+/// Runs no callbacks, see [`SelectManager::destroy_all`] for that. Joined, grouped or limited queries
+/// delete the rows whose primary keys the query returns, the same sub select works on every backend
+/// where `DELETE .. USING` and multi-table `DELETE` differ.
+///
+/// # Examples
+///
+/// ```
+/// use arel::prelude::*;
+/// #[arel]
+/// struct User {}
+/// impl Arel for User {}
+/// #[arel]
+/// struct Wallet {}
+/// impl Arel for Wallet {}
+/// let delete_manager = User::query().r#where("type", "GUEST").delete_manager();
+/// #[cfg(feature = "sqlite")]
+/// assert_eq!(delete_manager.to_sql().unwrap().to_sql_string().unwrap(), r#"DELETE FROM "user" WHERE "user"."type" = ?"#);
+///
+/// let delete_manager = User::query().join::<Wallet>(arel::JoinConst::InnerJoin).where_sql(r#""wallet"."amount" = 0"#).delete_manager();
+/// #[cfg(not(feature = "mysql"))]
+/// assert_eq!(
+///     delete_manager.to_sql().unwrap().to_sql_string().unwrap(),
+///     r#"DELETE FROM "user" WHERE ("id") IN (SELECT * FROM (SELECT "user"."id" FROM "user" INNER JOIN "wallet" ON "user"."id" = "wallet"."user_id" WHERE "wallet"."amount" = 0) AS "arel_sub")"#
+/// );
+/// ```
+#[derive(Debug)]
+pub struct DeleteManager<M: crate::Arel> {
+    target: Option<crate::Sql>,
+    error: Option<String>,
+    _marker: PhantomData<M>,
+}
+
+impl<M: Arel> Clone for DeleteManager<M> {
+    fn clone(&self) -> Self {
+        Self {
+            target: self.target.clone(),
+            error: self.error.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<M: Arel> DeleteManager<M> {
+    pub fn new(query: &SelectManager<M>) -> Self {
+        let (target, error) = match query.to_target_filter_sql() {
+            Ok(target) => (target, None),
+            Err(err) => (None, Some(err.to_string())),
+        };
+        Self {
+            target,
+            error,
+            _marker: PhantomData,
+        }
+    }
+    pub fn to_sql(&self) -> crate::Result<crate::Sql> {
+        if let Some(message) = &self.error {
+            return Err(crate::Error::Message(message.clone()));
+        }
+        let mut final_sql = crate::Sql::new(format!("DELETE FROM {}", quote_ident(M::table_name())));
+        if let Some(target) = &self.target {
+            final_sql.push_str(" WHERE ").push_sql(target.clone());
+        }
+        Ok(final_sql)
+    }
+    /// Rows affected.
+    pub async fn execute_with_exec<'a, E>(&self, executor: E) -> crate::Result<u64>
+    where
+        E: sqlx::Executor<'a, Database = crate::db::Database>,
+    {
+        let result = self.to_sql()?.exec(executor).await?;
+        Ok(result.rows_affected())
+    }
+    pub async fn execute(&self) -> crate::Result<u64> {
+        self.execute_with_exec(M::pool()?).await
+    }
+}
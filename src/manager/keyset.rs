@@ -0,0 +1,97 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+struct CursorData {
+    values: Vec<crate::Value>,
+    backward: bool,
+}
+
+/// Position of a keyset page: the order column values of a boundary row.
+///
+/// Serializes to an opaque token, hand it back to [`crate::SelectManager::fetch_keyset_page`] as is.
+///
+/// # Examples
+///
+/// ```
+/// use arel::manager::Cursor;
+/// let cursor = Cursor::after(vec![arel::Value::from(10), arel::Value::from("sanmu")]);
+/// let token = cursor.encode().unwrap();
+/// assert_eq!(Cursor::decode(&token).unwrap(), cursor);
+/// assert!(Cursor::decode("not a cursor").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cursor {
+    values: Vec<crate::Value>,
+    backward: bool,
+}
+
+impl Cursor {
+    /// Rows following the row holding `values`.
+    pub fn after(values: Vec<crate::Value>) -> Self {
+        Self { values, backward: false }
+    }
+    /// Rows preceding the row holding `values`.
+    pub fn before(values: Vec<crate::Value>) -> Self {
+        Self { values, backward: true }
+    }
+    pub fn values(&self) -> &[crate::Value] {
+        &self.values
+    }
+    pub fn is_backward(&self) -> bool {
+        self.backward
+    }
+    pub fn encode(&self) -> crate::Result<String> {
+        let data = CursorData {
+            values: self.values.clone(),
+            backward: self.backward,
+        };
+        let bytes = serde_json::to_vec(&data)?;
+        Ok(bytes.iter().map(|byte| format!("{:02x}", byte)).collect())
+    }
+    pub fn decode<T: AsRef<str>>(token: T) -> crate::Result<Self> {
+        let token = token.as_ref();
+        let invalid = || crate::Error::Message(format!("invalid cursor: {}", token));
+        if token.len() % 2 != 0 || !token.is_ascii() {
+            return Err(invalid());
+        }
+        let bytes = (0..token.len())
+            .step_by(2)
+            .map(|idx| u8::from_str_radix(&token[idx..idx + 2], 16))
+            .collect::<Result<Vec<u8>, _>>()
+            .map_err(|_| invalid())?;
+        let data: CursorData = serde_json::from_slice(&bytes).map_err(|_| invalid())?;
+        Ok(Self {
+            values: data.values,
+            backward: data.backward,
+        })
+    }
+}
+
+impl Serialize for Cursor {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let token = self.encode().map_err(serde::ser::Error::custom)?;
+        serializer.serialize_str(&token)
+    }
+}
+impl<'de> Deserialize<'de> for Cursor {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let token = String::deserialize(deserializer)?;
+        Self::decode(token).map_err(serde::de::Error::custom)
+    }
+}
+
+/// A page returned by [`crate::SelectManager::fetch_keyset_page`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeysetPage<M> {
+    pub items: Vec<M>,
+    /// `None` on the last page
+    pub next_cursor: Option<Cursor>,
+    /// `None` on the first page
+    pub prev_cursor: Option<Cursor>,
+}
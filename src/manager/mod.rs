@@ -1,3 +1,13 @@
+pub mod compound_manager;
+pub mod delete_manager;
+pub mod keyset;
+pub mod page;
 pub mod select_manager;
+pub mod update_manager;
 
+pub use compound_manager::{CompoundConst, CompoundManager};
+pub use delete_manager::DeleteManager;
+pub use keyset::{Cursor, KeysetPage};
+pub use page::Page;
 pub use select_manager::SelectManager;
+pub use update_manager::UpdateManager;
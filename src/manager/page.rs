@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+/// A page returned by [`crate::SelectManager::fetch_page`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Page<M> {
+    pub items: Vec<M>,
+    /// rows of the query without order, limit and offset
+    pub total: i64,
+    pub total_pages: i64,
+    /// current page, starts from 1
+    pub page: usize,
+    pub per: usize,
+    pub has_next: bool,
+}
+
+impl<M> Page<M> {
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::manager::Page;
+    /// let page = Page::new(vec![1, 2], 12, 2, 5);
+    /// assert_eq!((page.total_pages, page.has_next), (3, true));
+    /// let page = Page::new(Vec::<i32>::new(), 0, 1, 5);
+    /// assert_eq!((page.total_pages, page.has_next), (0, false));
+    /// ```
+    pub fn new(items: Vec<M>, total: i64, page: usize, per: usize) -> Self {
+        let page = std::cmp::max(page, 1);
+        let total_pages = if per == 0 { 0 } else { (total + per as i64 - 1) / per as i64 };
+        Self {
+            items,
+            total,
+            total_pages,
+            page,
+            per,
+            has_next: (page as i64) < total_pages,
+        }
+    }
+}
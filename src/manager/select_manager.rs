@@ -1,9 +1,52 @@
 use crate::prelude::*;
+use crate::sql::{quote_column, quote_ident};
+use crate::statements::column::{ColumnFilter, ColumnName};
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::Hash;
 use std::marker::PhantomData;
 use std::ops::RangeBounds;
 
+// clause overrides for helpers that render a variant of the query without mutating it
+#[derive(Default)]
+struct RenderOptions {
+    skip_order: bool,
+    skip_limit: bool,
+    skip_lock: bool,
+    and_where: Option<crate::Sql>,
+    order: Option<crate::Sql>,
+    limit: Option<usize>,
+    offset: Option<usize>,
+}
+
+// where find_in_batches continues from
+enum BatchPosition {
+    Start,
+    After(Vec<crate::Value>),
+    Done,
+}
+
+// what `Arel::default_scope` added to a query, so `unscoped` drops it and keeps the clauses chained after:
+// the leading items of the list clauses, and the single clauses no later call replaced
+#[derive(Debug, Clone, Default)]
+struct DefaultScope {
+    with: usize,
+    join: usize,
+    r#where: usize,
+    having: usize,
+    order: usize,
+    select: bool,
+    group: bool,
+    limit: bool,
+    offset: bool,
+    lock: bool,
+    // `or` moved the scoped predicates into a group
+    where_nested: bool,
+}
+
 #[derive(Debug)]
 pub struct SelectManager<M: crate::Arel> {
+    with: Option<crate::statements::with::With>,
     select: crate::statements::select::Select<M>,
     join: Option<crate::statements::join::Join<M>>,
     r#where: Option<crate::statements::r#where::Where<M>>,
@@ -13,12 +56,34 @@ pub struct SelectManager<M: crate::Arel> {
     limit: Option<crate::statements::limit::Limit>,
     offset: Option<crate::statements::offset::Offset>,
     lock: Option<crate::statements::lock::Lock>,
+    default_scope: Option<DefaultScope>,
     _marker: PhantomData<M>,
 }
 
+// not derived, that would require `M: Clone`
+impl<M: Arel> Clone for SelectManager<M> {
+    fn clone(&self) -> Self {
+        Self {
+            with: self.with.clone(),
+            select: self.select.clone(),
+            join: self.join.clone(),
+            r#where: self.r#where.clone(),
+            group: self.group.clone(),
+            having: self.having.clone(),
+            order: self.order.clone(),
+            limit: self.limit.clone(),
+            offset: self.offset.clone(),
+            lock: self.lock.clone(),
+            default_scope: self.default_scope.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
 impl<M: Arel> Default for SelectManager<M> {
     fn default() -> Self {
         Self {
+            with: None,
             select: crate::statements::select::Select::<M>::default(),
             join: None,
             r#where: None,
@@ -28,6 +93,7 @@ impl<M: Arel> Default for SelectManager<M> {
             limit: None,
             offset: None,
             lock: None,
+            default_scope: None,
             _marker: PhantomData,
         }
     }
@@ -43,15 +109,18 @@ impl<M: Arel> SelectManager<M> {
     /// struct User {}
     /// impl Arel for User {}
     /// let mut select_manager = SelectManager::<User>::default();
+    /// #[cfg(not(feature = "mysql"))]
     /// assert_eq!(select_manager.to_sql().unwrap().to_sql_string().unwrap(), r#"SELECT "user".* FROM "user""#);
     ///
     /// select_manager.select(vec!["id", "name"]);
+    /// #[cfg(not(feature = "mysql"))]
     /// assert_eq!(select_manager.to_sql().unwrap().to_sql_string().unwrap(), r#"SELECT "user"."id", "user"."name" FROM "user""#);
     ///
     /// ```
-    pub fn select<T: AsRef<str>>(&mut self, columns: Vec<T>) -> &mut Self {
+    pub fn select<T: ColumnName<M>>(&mut self, columns: Vec<T>) -> &mut Self {
         let select = crate::statements::select::Select::<M>::new(columns);
-        self.select = select;
+        self.select.replace(select);
+        self.unmark_scope(|scope| scope.select = false);
         self
     }
     /// # Examples
@@ -64,14 +133,177 @@ impl<M: Arel> SelectManager<M> {
     /// impl Arel for User {}
     /// let mut select_manager = SelectManager::<User>::default();
     /// select_manager.select_sql("COUNT(*)");
+    /// #[cfg(not(feature = "mysql"))]
     /// assert_eq!(select_manager.to_sql().unwrap().to_sql_string().unwrap(), r#"SELECT COUNT(*) FROM "user""#);
     ///
     /// ```
     pub fn select_sql<S: Into<crate::Sql>>(&mut self, sql: S) -> &mut Self {
         let select = crate::statements::select::Select::<M>::new_sql(sql);
-        self.select = select;
+        self.select.replace(select);
+        self.unmark_scope(|scope| scope.select = false);
+        self
+    }
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// use arel::manager::SelectManager;
+    /// use arel::statements::expr::{Expr, Window};
+    /// #[arel]
+    /// struct User {
+    ///     id: i64,
+    ///     score: i32,
+    ///     #[arel(rename = "type")]
+    ///     r#type: String,
+    /// }
+    /// impl Arel for User {}
+    /// let columns = User::columns();
+    /// let mut select_manager = SelectManager::<User>::default();
+    /// select_manager.select_expr(vec![
+    ///     Expr::from(columns.id),
+    ///     Expr::rank().over(Window::new().partition_by(columns.r#type).order_by(columns.score, arel::SortConst::Desc)).alias("rank"),
+    ///     Expr::coalesce(vec![Expr::lag(columns.score, 1).over(Window::new().order_by(columns.id, arel::SortConst::Asc)), Expr::raw("0")]).alias("prev_score"),
+    /// ]);
+    /// #[cfg(not(feature = "mysql"))]
+    /// assert_eq!(
+    ///     select_manager.to_sql().unwrap().to_sql_string().unwrap(),
+    ///     r#"SELECT "user"."id", RANK() OVER (PARTITION BY "user"."type" ORDER BY "user"."score" DESC) AS "rank", COALESCE(LAG("user"."score", 1) OVER (ORDER BY "user"."id" ASC), 0) AS "prev_score" FROM "user""#
+    /// );
+    /// ```
+    pub fn select_expr<E: Into<crate::Expr>>(&mut self, exprs: Vec<E>) -> &mut Self {
+        let select = crate::statements::select::Select::<M>::new_sqls(exprs.into_iter().map(|expr| expr.into().into_sql()).collect());
+        self.select.replace(select);
+        self.unmark_scope(|scope| scope.select = false);
+        self
+    }
+    /// Append a scalar sub query to the projection, the projection starts from `"table".*` when nothing was selected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// use arel::manager::SelectManager;
+    /// #[arel]
+    /// struct User {}
+    /// impl Arel for User {}
+    /// #[arel]
+    /// struct Wallet {}
+    /// impl Arel for Wallet {}
+    /// let mut select_manager = SelectManager::<User>::default();
+    /// select_manager.select_query("wallet_count", Wallet::query().select_sql("COUNT(*)").where_sql(r#""wallet"."user_id" = "user"."id""#).r#where("currency", "USD"));
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(
+    ///     select_manager.to_sql().unwrap().to_sql_string().unwrap(),
+    ///     r#"SELECT "user".*, (SELECT COUNT(*) FROM "wallet" WHERE "wallet"."user_id" = "user"."id" AND "wallet"."currency" = ?) AS "wallet_count" FROM "user""#
+    /// );
+    ///
+    /// // a query that can not build fails the outer `to_sql`
+    /// #[cfg(feature = "sqlite")]
+    /// assert!(User::query().select_query("locked", Wallet::query().lock()).to_sql().is_err());
+    /// ```
+    pub fn select_query<A: AsRef<str>, U: Arel>(&mut self, alias: A, query: &SelectManager<U>) -> &mut Self {
+        match query.to_sql() {
+            Ok(query_sql) => {
+                let mut sql = crate::Sql::new("(");
+                sql.push_sql(query_sql).push_str(format!(") AS {}", quote_ident(alias)));
+                self.select.append_sql(sql);
+                self.unmark_scope(|scope| scope.select = false);
+            }
+            Err(err) => self.defer_error(err),
+        }
+        self
+    }
+    /// Prepend `WITH "name" AS (query)`, read it with [`SelectManager::from_with`] or [`SelectManager::join_with`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// use arel::manager::SelectManager;
+    /// #[arel]
+    /// struct User {}
+    /// impl Arel for User {}
+    /// #[arel]
+    /// struct Wallet {}
+    /// impl Arel for Wallet {}
+    /// let mut select_manager = SelectManager::<User>::default();
+    /// select_manager
+    ///     .with("rich", Wallet::query().select(vec!["user_id"]).where_gt("amount", 100))
+    ///     .join_with(arel::JoinConst::InnerJoin, "rich", r#""rich"."user_id" = "user"."id""#)
+    ///     .r#where("name", "sanmu");
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(
+    ///     select_manager.to_sql().unwrap().to_sql_string().unwrap(),
+    ///     r#"WITH "rich" AS (SELECT "wallet"."user_id" FROM "wallet" WHERE "wallet"."amount" > ?) SELECT "user".* FROM "user" INNER JOIN "rich" ON "rich"."user_id" = "user"."id" WHERE "user"."name" = ?"#
+    /// );
+    /// #[cfg(feature = "postgres")]
+    /// assert_eq!(
+    ///     select_manager.to_sql().unwrap().to_sql_string().unwrap(),
+    ///     r#"WITH "rich" AS (SELECT "wallet"."user_id" FROM "wallet" WHERE "wallet"."amount" > $1) SELECT "user".* FROM "user" INNER JOIN "rich" ON "rich"."user_id" = "user"."id" WHERE "user"."name" = $2"#
+    /// );
+    ///
+    /// // a query that can not build fails the outer `to_sql`
+    /// #[cfg(feature = "sqlite")]
+    /// assert!(User::query().with("locked", Wallet::query().lock()).r#where("name", "sanmu").to_sql().is_err());
+    /// ```
+    pub fn with<N: AsRef<str>, U: Arel>(&mut self, name: N, query: &SelectManager<U>) -> &mut Self {
+        match query.to_sql() {
+            Ok(sql) => {
+                self.with.get_or_insert_with(Default::default).append(name, sql);
+            }
+            Err(err) => self.defer_error(err),
+        }
+        self
+    }
+    /// Prepend `WITH RECURSIVE "name" AS (anchor UNION ALL recursive_part)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// use arel::manager::SelectManager;
+    /// #[arel]
+    /// struct Category {}
+    /// impl Arel for Category {}
+    /// let mut select_manager = SelectManager::<Category>::default();
+    /// select_manager
+    ///     .with_recursive(
+    ///         "tree",
+    ///         Category::query().r#where("id", 1),
+    ///         Category::query().join_sql(r#"INNER JOIN "tree" ON "category"."parent_id" = "tree"."id""#),
+    ///     )
+    ///     .from_with("tree");
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(
+    ///     select_manager.to_sql().unwrap().to_sql_string().unwrap(),
+    ///     r#"WITH RECURSIVE "tree" AS (SELECT "category".* FROM "category" WHERE "category"."id" = ? UNION ALL SELECT "category".* FROM "category" INNER JOIN "tree" ON "category"."parent_id" = "tree"."id") SELECT "category".* FROM "tree" AS "category""#
+    /// );
+    /// ```
+    pub fn with_recursive<N: AsRef<str>, A: Arel, R: Arel>(&mut self, name: N, anchor: &SelectManager<A>, recursive_part: &SelectManager<R>) -> &mut Self {
+        match (anchor.to_sql(), recursive_part.to_sql()) {
+            (Ok(anchor_sql), Ok(recursive_sql)) => {
+                self.with.get_or_insert_with(Default::default).append_recursive(name, anchor_sql, recursive_sql);
+            }
+            (Err(err), _) | (_, Err(err)) => self.defer_error(err),
+        }
+        self
+    }
+    // a subquery that failed to build, raised by `to_sql` like the errors of `where_in_query`
+    fn defer_error(&mut self, err: crate::Error) {
+        self.r#where.get_or_insert_with(Default::default).and_filter_error(err);
+    }
+    /// Read rows from a CTE instead of the table, the CTE is aliased as the table so column references keep working.
+    pub fn from_with<N: AsRef<str>>(&mut self, name: N) -> &mut Self {
+        self.select.from_sql(format!("{} AS {}", quote_ident(name), quote_ident(M::table_name())));
+        self.unmark_scope(|scope| scope.select = false);
         self
     }
+    /// Join a CTE by name: `{join_type} "name" ON {on}`.
+    pub fn join_with<N: AsRef<str>, S: Into<crate::Sql>>(&mut self, join_type: crate::JoinConst, name: N, on: S) -> &mut Self {
+        let mut sql = crate::Sql::new(format!("{} {} ON ", join_type, quote_ident(name)));
+        sql.push_sql(on.into());
+        self.join_sql(sql)
+    }
     /// # Examples
     ///
     /// ```
@@ -82,11 +314,13 @@ impl<M: Arel> SelectManager<M> {
     /// impl Arel for User {}
     /// let mut select_manager = SelectManager::<User>::default();
     /// select_manager.distinct();
+    /// #[cfg(not(feature = "mysql"))]
     /// assert_eq!(select_manager.to_sql().unwrap().to_sql_string().unwrap(), r#"SELECT DISTINCT "user".* FROM "user""#);
     ///
     /// ```
     pub fn distinct(&mut self) -> &mut Self {
         self.select.distinct();
+        self.unmark_scope(|scope| scope.select = false);
         self
     }
     /// # Examples
@@ -102,6 +336,7 @@ impl<M: Arel> SelectManager<M> {
     /// impl Arel for Wallet {}
     /// let mut select_manager = SelectManager::<User>::default();
     /// select_manager.join::<Wallet>(arel::JoinConst::InnerJoin);
+    /// #[cfg(not(feature = "mysql"))]
     /// assert_eq!(select_manager.to_sql().unwrap().to_sql_string().unwrap(), r#"SELECT "user".* FROM "user" INNER JOIN "wallet" ON "user"."id" = "wallet"."user_id""#);
     /// ```
     pub fn join<U: Arel>(&mut self, join_type: crate::JoinConst) -> &mut Self {
@@ -130,6 +365,7 @@ impl<M: Arel> SelectManager<M> {
     /// impl Arel for User {}
     /// let mut select_manager = SelectManager::<User>::default();
     /// select_manager.join_sql("LEFT JOIN wallet on user.id = wallet.user_id");
+    /// #[cfg(not(feature = "mysql"))]
     /// assert_eq!(select_manager.to_sql().unwrap().to_sql_string().unwrap(), r#"SELECT "user".* FROM "user" LEFT JOIN wallet on user.id = wallet.user_id"#);
     /// ```
     pub fn join_sql<S: Into<crate::Sql>>(&mut self, sql: S) -> &mut Self {
@@ -142,7 +378,24 @@ impl<M: Arel> SelectManager<M> {
         }
         self
     }
-    pub fn r#where<K: AsRef<str>, V: Into<crate::Value>>(&mut self, key: K, value: V) -> &mut Self {
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// use arel::manager::SelectManager;
+    /// #[arel]
+    /// struct User {
+    ///     age: Option<i32>,
+    ///     #[arel(rename = "type")]
+    ///     r#type: String,
+    /// }
+    /// impl Arel for User {}
+    /// let mut select_manager = SelectManager::<User>::default();
+    /// select_manager.r#where(User::columns().age, 18).r#where(User::columns().r#type, "admin").r#where("id", vec![1, 2]);
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(select_manager.to_sql().unwrap().to_sql_string().unwrap(), r#"SELECT "user".* FROM "user" WHERE "user"."age" = ? AND "user"."type" = ? AND "user"."id" IN (?, ?)"#);
+    /// ```
+    pub fn r#where<K: ColumnFilter<M, V, X>, V, X>(&mut self, key: K, value: V) -> &mut Self {
         if let Some(r#where) = &mut self.r#where {
             r#where.and_filter(key, value);
         } else {
@@ -153,12 +406,12 @@ impl<M: Arel> SelectManager<M> {
         self
     }
     #[allow(non_snake_case)]
-    pub fn Where<K: AsRef<str>, V: Into<crate::Value>>(&mut self, key: K, value: V) -> &mut Self {
+    pub fn Where<K: ColumnFilter<M, V, X>, V, X>(&mut self, key: K, value: V) -> &mut Self {
         self.r#where(key, value)
     }
-    pub fn where_range<K: AsRef<str>, V: ToString, R: RangeBounds<V>>(&mut self, key: K, range: R) -> &mut Self {
-        if let Some(sql) = crate::Sql::range_sql(key, range) {
-            self.where_sql(sql);
+    pub fn where_range<K: ColumnName<M>, V: ToString, R: RangeBounds<V>>(&mut self, key: K, range: R) -> &mut Self {
+        if let Some(sql) = crate::Sql::range_sql(key.column_name(), range) {
+            self.r#where.get_or_insert_with(Default::default).and_filter_column_sql(key.column_name(), sql);
         }
         self
     }
@@ -172,7 +425,7 @@ impl<M: Arel> SelectManager<M> {
         }
         self
     }
-    pub fn where_not<K: AsRef<str>, V: Into<crate::Value>>(&mut self, key: K, value: V) -> &mut Self {
+    pub fn where_not<K: ColumnFilter<M, V, X>, V, X>(&mut self, key: K, value: V) -> &mut Self {
         if let Some(r#where) = &mut self.r#where {
             r#where.and_not_filter(key, value);
         } else {
@@ -182,7 +435,7 @@ impl<M: Arel> SelectManager<M> {
         }
         self
     }
-    pub fn where_or<K: AsRef<str>, V: Into<crate::Value>>(&mut self, key: K, value: V) -> &mut Self {
+    pub fn where_or<K: ColumnFilter<M, V, X>, V, X>(&mut self, key: K, value: V) -> &mut Self {
         if let Some(r#where) = &mut self.r#where {
             r#where.or_filter(key, value);
         } else {
@@ -192,178 +445,1403 @@ impl<M: Arel> SelectManager<M> {
         }
         self
     }
-    pub fn group<T: AsRef<str>>(&mut self, columns: Vec<T>) -> &mut Self {
-        let group = crate::statements::group::Group::<M>::new(columns);
-        self.group = Some(group);
+    pub fn where_or_not<K: ColumnFilter<M, V, X>, V, X>(&mut self, key: K, value: V) -> &mut Self {
+        self.r#where.get_or_insert_with(Default::default).or_not_filter(key, value);
         self
     }
-    pub fn having<K: AsRef<str>, V: Into<crate::Value>>(&mut self, key: K, value: V) -> &mut Self {
-        if let Some(having) = &mut self.having {
-            having.and_filter(key, value);
-        } else {
-            let mut having = crate::statements::having::Having::<M>::default();
-            having.and_filter(key, value);
-            self.having = Some(having);
-        }
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// use arel::manager::SelectManager;
+    /// #[arel]
+    /// struct User {
+    ///     age: Option<i32>,
+    /// }
+    /// impl Arel for User {}
+    /// let mut select_manager = SelectManager::<User>::default();
+    /// select_manager.where_gte(User::columns().age, 18).where_lt("age", 30).where_null_safe_ne("name", Option::<String>::None);
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(select_manager.to_sql().unwrap().to_sql_string().unwrap(), r#"SELECT "user".* FROM "user" WHERE "user"."age" >= ? AND "user"."age" < ? AND "user"."name" IS NOT ?"#);
+    /// #[cfg(feature = "postgres")]
+    /// assert_eq!(select_manager.to_sql().unwrap().to_sql_string().unwrap(), r#"SELECT "user".* FROM "user" WHERE "user"."age" >= $1 AND "user"."age" < $2 AND "user"."name" IS DISTINCT FROM $3"#);
+    /// ```
+    pub fn where_gt<K: ColumnFilter<M, V, X>, V, X>(&mut self, key: K, value: V) -> &mut Self {
+        self.r#where.get_or_insert_with(Default::default).and_filter_operator(key, crate::statements::filter::Operator::Gt, value);
         self
     }
-    pub fn having_sql<S: Into<crate::Sql>>(&mut self, sql: S) -> &mut Self {
-        if let Some(having) = &mut self.having {
-            having.and_filter_sql(sql);
-        } else {
-            let mut having = crate::statements::having::Having::<M>::default();
-            having.and_filter_sql(sql);
-            self.having = Some(having);
-        }
+    pub fn where_gte<K: ColumnFilter<M, V, X>, V, X>(&mut self, key: K, value: V) -> &mut Self {
+        self.r#where.get_or_insert_with(Default::default).and_filter_operator(key, crate::statements::filter::Operator::Gte, value);
         self
     }
-    pub fn having_not<K: AsRef<str>, V: Into<crate::Value>>(&mut self, key: K, value: V) -> &mut Self {
-        if let Some(having) = &mut self.having {
-            having.and_not_filter(key, value);
-        } else {
-            let mut having = crate::statements::having::Having::<M>::default();
-            having.and_not_filter(key, value);
-            self.having = Some(having);
-        }
+    pub fn where_lt<K: ColumnFilter<M, V, X>, V, X>(&mut self, key: K, value: V) -> &mut Self {
+        self.r#where.get_or_insert_with(Default::default).and_filter_operator(key, crate::statements::filter::Operator::Lt, value);
         self
     }
-    pub fn having_or<K: AsRef<str>, V: Into<crate::Value>>(&mut self, key: K, value: V) -> &mut Self {
-        if let Some(having) = &mut self.having {
-            having.or_filter(key, value);
-        } else {
-            let mut having = crate::statements::having::Having::<M>::default();
-            having.or_filter(key, value);
-            self.having = Some(having);
-        }
+    pub fn where_lte<K: ColumnFilter<M, V, X>, V, X>(&mut self, key: K, value: V) -> &mut Self {
+        self.r#where.get_or_insert_with(Default::default).and_filter_operator(key, crate::statements::filter::Operator::Lte, value);
         self
     }
-    pub fn order<T: AsRef<str>>(&mut self, column: T, sort_type: crate::SortConst) -> &mut Self {
-        if let Some(order) = &mut self.order {
-            order.append(column, sort_type);
-        } else {
-            let order = crate::statements::order::Order::<M>::new(column, sort_type);
-            self.order = Some(order);
-        }
+    pub fn where_null_safe_eq<K: ColumnFilter<M, V, X>, V, X>(&mut self, key: K, value: V) -> &mut Self {
+        self.r#where.get_or_insert_with(Default::default).and_filter_operator(key, crate::statements::filter::Operator::NullSafeEq, value);
         self
     }
-    pub fn order_asc<T: AsRef<str>>(&mut self, column: T) -> &mut Self {
-        self.order(column, crate::SortConst::Asc)
+    pub fn where_null_safe_ne<K: ColumnFilter<M, V, X>, V, X>(&mut self, key: K, value: V) -> &mut Self {
+        self.r#where.get_or_insert_with(Default::default).and_filter_operator(key, crate::statements::filter::Operator::NullSafeNe, value);
+        self
     }
-    pub fn order_desc<T: AsRef<str>>(&mut self, column: T) -> &mut Self {
-        self.order(column, crate::SortConst::Desc)
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// use arel::manager::SelectManager;
+    /// #[arel]
+    /// struct User {}
+    /// impl Arel for User {}
+    /// let mut select_manager = SelectManager::<User>::default();
+    /// select_manager.where_between("age", 18, 30).where_not_between("age", 20, 25);
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(select_manager.to_sql().unwrap().to_sql_string().unwrap(), r#"SELECT "user".* FROM "user" WHERE "user"."age" BETWEEN ? AND ? AND "user"."age" NOT BETWEEN ? AND ?"#);
+    /// ```
+    pub fn where_between<K: ColumnFilter<M, V, X>, V, X>(&mut self, key: K, start: V, end: V) -> &mut Self {
+        self.r#where.get_or_insert_with(Default::default).and_filter_between(key, start, end);
+        self
     }
-    pub fn limit(&mut self, num: usize) -> &mut Self {
-        let limit = crate::statements::limit::Limit::new(num);
-        self.limit = Some(limit);
+    pub fn where_not_between<K: ColumnFilter<M, V, X>, V, X>(&mut self, key: K, start: V, end: V) -> &mut Self {
+        self.r#where.get_or_insert_with(Default::default).and_not_filter_between(key, start, end);
         self
     }
-    pub fn offset(&mut self, num: usize) -> &mut Self {
-        let offset = crate::statements::offset::Offset::new(num);
-        self.offset = Some(offset);
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// use arel::manager::SelectManager;
+    /// #[arel]
+    /// struct User {}
+    /// impl Arel for User {}
+    /// let mut select_manager = SelectManager::<User>::default();
+    /// select_manager.where_like("name", "san%").where_contains("email", "100%_");
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(
+    ///     select_manager.to_sql().unwrap().to_sql_string().unwrap(),
+    ///     r#"SELECT "user".* FROM "user" WHERE "user"."name" LIKE ? ESCAPE '\' AND "user"."email" LIKE ? ESCAPE '\'"#
+    /// );
+    /// #[cfg(feature = "postgres")]
+    /// assert_eq!(select_manager.to_sql().unwrap().to_sql_string().unwrap(), r#"SELECT "user".* FROM "user" WHERE "user"."name" LIKE $1 AND "user"."email" LIKE $2"#);
+    /// ```
+    pub fn where_like<K: ColumnName<M>, S: Into<String>>(&mut self, key: K, pattern: S) -> &mut Self {
+        self.r#where.get_or_insert_with(Default::default).and_filter_operator(key.column_name(), crate::statements::filter::Operator::Like, pattern.into());
         self
     }
-    pub fn paginate(&mut self, page: usize, page_size: usize) -> &mut Self {
-        let offset = (std::cmp::max(page, 1) - 1) * page_size;
-        self.limit(page_size);
-        self.offset(offset)
+    pub fn where_not_like<K: ColumnName<M>, S: Into<String>>(&mut self, key: K, pattern: S) -> &mut Self {
+        self.r#where.get_or_insert_with(Default::default).and_filter_operator(key.column_name(), crate::statements::filter::Operator::NotLike, pattern.into());
+        self
     }
-    pub fn lock(&mut self) -> &mut Self {
-        let lock = crate::statements::lock::Lock::new();
-        self.lock = Some(lock);
+    pub fn where_ilike<K: ColumnName<M>, S: Into<String>>(&mut self, key: K, pattern: S) -> &mut Self {
+        self.r#where.get_or_insert_with(Default::default).and_filter_operator(key.column_name(), crate::statements::filter::Operator::ILike, pattern.into());
         self
     }
-    pub fn to_sql(&self) -> crate::Result<crate::Sql> {
-        let table_name = M::table_name();
-        let mut final_sql = crate::Sql::new("");
-
-        let mut select_sql = crate::Sql::new(format!(r#"SELECT "{}".* FROM "{}""#, table_name, table_name));
-        if let Some(sql) = self.select.to_sql()? {
-            select_sql = sql;
-        }
-        final_sql.push_sql(select_sql);
-
-        if let Some(join) = &self.join {
-            if let Some(sql) = join.to_sql()? {
-                final_sql.push_str(" ").push_sql(sql);
-            }
-        }
-
-        if let Some(r#where) = &self.r#where {
-            if let Some(sql) = r#where.to_sql()? {
-                final_sql.push_str(" ").push_sql(sql);
-            }
-        }
-
-        if let Some(group) = &self.group {
-            if let Some(sql) = group.to_sql()? {
-                final_sql.push_str(" ").push_sql(sql);
-            }
-        }
-
-        if let Some(having) = &self.having {
-            if let Some(sql) = having.to_sql()? {
-                final_sql.push_str(" ").push_sql(sql);
-            }
-        }
-
-        if let Some(order) = &self.order {
-            if let Some(sql) = order.to_sql()? {
-                final_sql.push_str(" ").push_sql(sql);
-            }
-        }
-
-        if let Some(limit) = &self.limit {
-            if let Some(sql) = limit.to_sql()? {
-                final_sql.push_str(" ").push_sql(sql);
-            }
-        }
-
-        if let Some(offset) = &self.offset {
-            if let Some(sql) = offset.to_sql()? {
-                final_sql.push_str(" ").push_sql(sql);
-            }
-        }
-
-        if let Some(lock) = &self.lock {
-            if let Some(sql) = lock.to_sql()? {
-                final_sql.push_str(" ").push_sql(sql);
-            }
-        }
-
-        Ok(final_sql)
+    pub fn where_not_ilike<K: ColumnName<M>, S: Into<String>>(&mut self, key: K, pattern: S) -> &mut Self {
+        self.r#where.get_or_insert_with(Default::default).and_filter_operator(key.column_name(), crate::statements::filter::Operator::NotILike, pattern.into());
+        self
     }
-}
-
-impl<M: Arel> SelectManager<M>
-where
-    for<'b> M: Send + Unpin + sqlx::FromRow<'b, crate::db::DatabaseRow>,
-{
-    pub fn to_query_builder<'a>(&self) -> crate::Result<crate::sql::QueryBuilder<'a>> {
-        let sql = self.to_sql()?;
-        Ok(sql.try_into()?)
+    /// `LIKE 'value%'` with `%` and `_` in value matched literally
+    pub fn where_starts_with<K: ColumnName<M>, S: AsRef<str>>(&mut self, key: K, value: S) -> &mut Self {
+        self.where_like(key, format!("{}%", crate::Sql::escape_like(value)))
     }
-    pub async fn fetch_count_with_exec<'a, E>(&self, executor: E) -> crate::Result<i64>
+    /// `LIKE '%value'` with `%` and `_` in value matched literally
+    pub fn where_ends_with<K: ColumnName<M>, S: AsRef<str>>(&mut self, key: K, value: S) -> &mut Self {
+        self.where_like(key, format!("%{}", crate::Sql::escape_like(value)))
+    }
+    /// `LIKE '%value%'` with `%` and `_` in value matched literally
+    pub fn where_contains<K: ColumnName<M>, S: AsRef<str>>(&mut self, key: K, value: S) -> &mut Self {
+        self.where_like(key, format!("%{}%", crate::Sql::escape_like(value)))
+    }
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// use arel::manager::SelectManager;
+    /// #[arel]
+    /// struct User {}
+    /// impl Arel for User {}
+    /// let mut select_manager = SelectManager::<User>::default();
+    /// select_manager
+    ///     .where_group(|g| g.r#where("a", 1).where_or("b", 2))
+    ///     .where_group(|g| g.r#where("c", 3).where_or_not_group(|g| g.r#where("d", 4).where_or("e", 5)));
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(
+    ///     select_manager.to_sql().unwrap().to_sql_string().unwrap(),
+    ///     r#"SELECT "user".* FROM "user" WHERE ("user"."a" = ? OR "user"."b" = ?) AND ("user"."c" = ? OR NOT ("user"."d" = ? OR "user"."e" = ?))"#
+    /// );
+    /// ```
+    pub fn where_group<F>(&mut self, callback: F) -> &mut Self
     where
-        E: sqlx::Executor<'a, Database = crate::db::Database>,
+        F: FnOnce(&mut Self) -> &mut Self,
     {
-        let row: (i64,) = self.to_sql()?.fetch_one_as_with_exec(executor).await?;
-        Ok(row.0)
-    }
-    pub async fn fetch_count(&self) -> crate::Result<i64> {
-        self.fetch_count_with_exec(M::pool()?).await
+        if let Some(filter) = Self::build_where_group(callback) {
+            self.r#where.get_or_insert_with(Default::default).and_filter_group(filter);
+        }
+        self
     }
-    pub(crate) async fn fetch_one_as_with_exec<'a, T, E>(&self, executor: E) -> crate::Result<T>
+    pub fn where_not_group<F>(&mut self, callback: F) -> &mut Self
     where
-        for<'b> T: Send + Unpin + sqlx::FromRow<'b, crate::db::DatabaseRow>,
-        E: sqlx::Executor<'a, Database = crate::db::Database>,
+        F: FnOnce(&mut Self) -> &mut Self,
     {
-        let ret: T = self.to_sql()?.fetch_one_as_with_exec(executor).await?;
-        Ok(ret)
+        if let Some(filter) = Self::build_where_group(callback) {
+            self.r#where.get_or_insert_with(Default::default).and_not_filter_group(filter);
+        }
+        self
     }
-    pub(crate) async fn fetch_one_optional_as_with_exec<'a, T, E>(&self, executor: E) -> crate::Result<Option<T>>
+    pub fn where_or_group<F>(&mut self, callback: F) -> &mut Self
     where
-        for<'b> T: Send + Unpin + sqlx::FromRow<'b, crate::db::DatabaseRow>,
-        E: sqlx::Executor<'a, Database = crate::db::Database>,
+        F: FnOnce(&mut Self) -> &mut Self,
+    {
+        if let Some(filter) = Self::build_where_group(callback) {
+            self.r#where.get_or_insert_with(Default::default).or_filter_group(filter);
+        }
+        self
+    }
+    pub fn where_or_not_group<F>(&mut self, callback: F) -> &mut Self
+    where
+        F: FnOnce(&mut Self) -> &mut Self,
+    {
+        if let Some(filter) = Self::build_where_group(callback) {
+            self.r#where.get_or_insert_with(Default::default).or_not_filter_group(filter);
+        }
+        self
+    }
+    fn build_where_group<F>(callback: F) -> Option<crate::statements::filter::Filter<M>>
+    where
+        F: FnOnce(&mut Self) -> &mut Self,
+    {
+        let mut group = Self::default();
+        callback(&mut group);
+        group.r#where.map(|r#where| r#where.into_inner())
+    }
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// use arel::manager::SelectManager;
+    /// #[arel]
+    /// struct User {}
+    /// impl Arel for User {}
+    /// #[arel]
+    /// struct Wallet {}
+    /// impl Arel for Wallet {}
+    /// let mut select_manager = SelectManager::<User>::default();
+    /// select_manager.r#where("name", "sanmu").where_in_query("id", Wallet::query().select(vec!["user_id"]).where_gt("amount", 100));
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(
+    ///     select_manager.to_sql().unwrap().to_sql_string().unwrap(),
+    ///     r#"SELECT "user".* FROM "user" WHERE "user"."name" = ? AND "user"."id" IN (SELECT "wallet"."user_id" FROM "wallet" WHERE "wallet"."amount" > ?)"#
+    /// );
+    /// #[cfg(feature = "postgres")]
+    /// assert_eq!(
+    ///     select_manager.to_sql().unwrap().to_sql_string().unwrap(),
+    ///     r#"SELECT "user".* FROM "user" WHERE "user"."name" = $1 AND "user"."id" IN (SELECT "wallet"."user_id" FROM "wallet" WHERE "wallet"."amount" > $2)"#
+    /// );
+    /// ```
+    pub fn where_in_query<K: ColumnName<M>, U: Arel>(&mut self, key: K, query: &SelectManager<U>) -> &mut Self {
+        let r#where = self.r#where.get_or_insert_with(Default::default);
+        match query.to_sql() {
+            Ok(sql) => r#where.and_filter_in_sql(key, sql),
+            Err(err) => r#where.and_filter_error(err),
+        };
+        self
+    }
+    pub fn where_not_in_query<K: ColumnName<M>, U: Arel>(&mut self, key: K, query: &SelectManager<U>) -> &mut Self {
+        let r#where = self.r#where.get_or_insert_with(Default::default);
+        match query.to_sql() {
+            Ok(sql) => r#where.and_not_filter_in_sql(key, sql),
+            Err(err) => r#where.and_filter_error(err),
+        };
+        self
+    }
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// use arel::manager::SelectManager;
+    /// #[arel]
+    /// struct User {}
+    /// impl Arel for User {}
+    /// #[arel]
+    /// struct Wallet {}
+    /// impl Arel for Wallet {}
+    /// let mut select_manager = SelectManager::<User>::default();
+    /// select_manager.where_exists(Wallet::query().select_sql("1").where_sql(r#""wallet"."user_id" = "user"."id""#).r#where("currency", "USD"));
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(
+    ///     select_manager.to_sql().unwrap().to_sql_string().unwrap(),
+    ///     r#"SELECT "user".* FROM "user" WHERE EXISTS (SELECT 1 FROM "wallet" WHERE "wallet"."user_id" = "user"."id" AND "wallet"."currency" = ?)"#
+    /// );
+    /// ```
+    pub fn where_exists<U: Arel>(&mut self, query: &SelectManager<U>) -> &mut Self {
+        self.where_exists_sql("EXISTS (", query)
+    }
+    pub fn where_not_exists<U: Arel>(&mut self, query: &SelectManager<U>) -> &mut Self {
+        self.where_exists_sql("NOT EXISTS (", query)
+    }
+    fn where_exists_sql<U: Arel>(&mut self, exists_str: &str, query: &SelectManager<U>) -> &mut Self {
+        let r#where = self.r#where.get_or_insert_with(Default::default);
+        match query.to_sql() {
+            Ok(sql) => {
+                let mut exists_sql = crate::Sql::new(exists_str);
+                exists_sql.push_sql(sql).push_str(")");
+                r#where.and_filter_sql(exists_sql)
+            }
+            Err(err) => r#where.and_filter_error(err),
+        };
+        self
+    }
+    /// Combine the clauses of `other` into this query: its where and having predicates are ANDed as a group,
+    /// its joins, group columns and orders appended.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// #[arel]
+    /// struct User {}
+    /// impl Arel for User {}
+    /// #[arel]
+    /// struct Wallet {}
+    /// impl Arel for Wallet {}
+    /// let mut base = User::query();
+    /// base.where_gt("age", 18);
+    /// let mut rich = User::query();
+    /// rich.join::<Wallet>(arel::JoinConst::InnerJoin).where_sql(r#""wallet"."amount" > 100"#).where_or("type", "VIP").order_desc("id");
+    /// let mut query = base.clone();
+    /// query.merge(&rich);
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(
+    ///     query.to_sql().unwrap().to_sql_string().unwrap(),
+    ///     r#"SELECT "user".* FROM "user" INNER JOIN "wallet" ON "user"."id" = "wallet"."user_id" WHERE "user"."age" > ? AND ("wallet"."amount" > 100 OR "user"."type" = ?) ORDER BY "user"."id" DESC"#
+    /// );
+    /// // the base query is untouched
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(base.to_sql().unwrap().to_sql_string().unwrap(), r#"SELECT "user".* FROM "user" WHERE "user"."age" > ?"#);
+    /// ```
+    pub fn merge(&mut self, other: &Self) -> &mut Self {
+        if let Some(other_where) = &other.r#where {
+            match &mut self.r#where {
+                Some(r#where) => {
+                    r#where.and_filter_group((**other_where).clone());
+                }
+                None => self.r#where = Some(other_where.clone()),
+            }
+        }
+        if let Some(other_having) = &other.having {
+            match &mut self.having {
+                Some(having) => {
+                    having.and_filter_group((**other_having).clone());
+                }
+                None => self.having = Some(other_having.clone()),
+            }
+        }
+        if let Some(other_join) = &other.join {
+            self.join.get_or_insert_with(Default::default).merge(other_join);
+        }
+        if let Some(other_group) = &other.group {
+            match &mut self.group {
+                Some(group) => {
+                    group.merge(other_group);
+                    self.unmark_scope(|scope| scope.group = false);
+                }
+                None => self.group = Some(other_group.clone()),
+            }
+        }
+        if let Some(other_order) = &other.order {
+            self.order.get_or_insert_with(Default::default).merge(other_order);
+        }
+        self
+    }
+    /// `WHERE (this query's predicates) OR (other's predicates)`, the other clauses of `other` are ignored.
+    /// A side without predicates matches every row, so the where clause is dropped then.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// #[arel]
+    /// struct User {}
+    /// impl Arel for User {}
+    /// let mut query = User::query();
+    /// query.where_gt("age", 18).r#where("type", "ADMIN").order_desc("id");
+    /// query.or(User::query().r#where("name", "sanmu"));
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(
+    ///     query.to_sql().unwrap().to_sql_string().unwrap(),
+    ///     r#"SELECT "user".* FROM "user" WHERE ("user"."age" > ? AND "user"."type" = ?) OR ("user"."name" = ?) ORDER BY "user"."id" DESC"#
+    /// );
+    /// ```
+    pub fn or(&mut self, other: &Self) -> &mut Self {
+        self.unmark_scope(|scope| {
+            scope.where_nested |= scope.r#where > 0;
+            scope.r#where = 0;
+        });
+        self.r#where = match (self.r#where.take(), &other.r#where) {
+            (Some(r#where), Some(other_where)) => {
+                let mut new_where = crate::statements::r#where::Where::<M>::default();
+                new_where.and_filter_group(r#where.into_inner()).or_filter_group((**other_where).clone());
+                Some(new_where)
+            }
+            _ => None,
+        };
+        self
+    }
+    /// `UPDATE` of every row this query matches, see [`crate::manager::UpdateManager`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// use arel::statements::expr::Expr;
+    /// #[arel]
+    /// struct User {
+    ///     age: i32,
+    /// }
+    /// impl Arel for User {}
+    /// let mut update_manager = User::query().where_gt("age", 18).update_manager();
+    /// update_manager.set("status", "ADULT").set("age", Expr::from(User::columns().age) + 1);
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(
+    ///     update_manager.to_sql().unwrap().to_sql_string().unwrap(),
+    ///     r#"UPDATE "user" SET "status" = ?, "age" = ("user"."age" + ?) WHERE "user"."age" > ?"#
+    /// );
+    /// ```
+    pub fn update_manager(&self) -> crate::manager::UpdateManager<M> {
+        crate::manager::UpdateManager::new(self)
+    }
+    /// `DELETE` of every row this query matches, see [`crate::manager::DeleteManager`].
+    pub fn delete_manager(&self) -> crate::manager::DeleteManager<M> {
+        crate::manager::DeleteManager::new(self)
+    }
+    /// Drop the predicates on `column`, e.g. the ones added by [`Arel::default_scope`].
+    pub fn unscope_where<T: ColumnName<M>>(&mut self, column: T) -> &mut Self {
+        if let Some(r#where) = &mut self.r#where {
+            r#where.unfilter(column.column_name());
+        }
+        self
+    }
+    /// Drop what [`Arel::default_scope`] added to the query, the clauses chained after it stay.
+    ///
+    /// A scoped clause replaced later (`limit`, `reorder`, `select` ..) is the caller's and stays too. After
+    /// [`SelectManager::or`] the scoped predicates can not be told apart, `to_sql` fails then: call `unscoped` first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// #[arel]
+    /// struct User {}
+    /// impl Arel for User {
+    ///     fn default_scope(query: &mut arel::SelectManager<Self>) {
+    ///         query.r#where("tenant_id", 1).order_desc("id").limit(100);
+    ///     }
+    /// }
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(User::query().where_gt("age", 18).to_sql().unwrap().to_sql_string().unwrap(), r#"SELECT "user".* FROM "user" WHERE "user"."tenant_id" = ? AND "user"."age" > ? ORDER BY "user"."id" DESC LIMIT 100"#);
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(User::query().unscoped().where_gt("age", 18).to_sql().unwrap().to_sql_string().unwrap(), r#"SELECT "user".* FROM "user" WHERE "user"."age" > ?"#);
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(
+    ///     User::query().where_gt("age", 18).order_asc("name").limit(10).unscoped().to_sql().unwrap().to_sql_string().unwrap(),
+    ///     r#"SELECT "user".* FROM "user" WHERE "user"."age" > ? ORDER BY "user"."name" ASC LIMIT 10"#
+    /// );
+    /// assert!(User::query().or(&User::query().r#where("age", 18)).unscoped().to_sql().is_err());
+    /// ```
+    pub fn unscoped(&mut self) -> &mut Self {
+        let Some(scope) = self.default_scope.take() else {
+            return self;
+        };
+        if scope.where_nested {
+            self.defer_error(crate::Error::Message(format!("unscoped() after or() can not tell the default scope of {} apart, call it before or()", M::table_name())));
+            return self;
+        }
+        if let Some(with) = &mut self.with {
+            with.remove_leading(scope.with);
+        }
+        if let Some(join) = &mut self.join {
+            join.remove_leading(scope.join);
+        }
+        if let Some(r#where) = &mut self.r#where {
+            r#where.remove_leading(scope.r#where);
+        }
+        if let Some(having) = &mut self.having {
+            having.remove_leading(scope.having);
+        }
+        if let Some(order) = &mut self.order {
+            order.remove_leading(scope.order);
+        }
+        if scope.select {
+            self.select = Default::default();
+        }
+        if scope.group {
+            self.group = None;
+        }
+        if scope.limit {
+            self.limit = None;
+        }
+        if scope.offset {
+            self.offset = None;
+        }
+        if scope.lock {
+            self.lock = None;
+        }
+        self
+    }
+    /// Record the clauses [`Arel::default_scope`] just added, see [`SelectManager::unscoped`].
+    pub(crate) fn mark_default_scope(&mut self) {
+        self.default_scope = Some(DefaultScope {
+            with: self.with.as_ref().map_or(0, |with| with.len()),
+            join: self.join.as_ref().map_or(0, |join| join.len()),
+            r#where: self.r#where.as_ref().map_or(0, |r#where| r#where.len()),
+            having: self.having.as_ref().map_or(0, |having| having.len()),
+            order: self.order.as_ref().map_or(0, |order| order.len()),
+            select: true,
+            group: self.group.is_some(),
+            limit: self.limit.is_some(),
+            offset: self.offset.is_some(),
+            lock: self.lock.is_some(),
+            where_nested: false,
+        });
+    }
+    fn unmark_scope<F: FnOnce(&mut DefaultScope)>(&mut self, unmark: F) {
+        if let Some(scope) = &mut self.default_scope {
+            unmark(scope);
+        }
+    }
+    pub fn group<T: ColumnName<M>>(&mut self, columns: Vec<T>) -> &mut Self {
+        let group = crate::statements::group::Group::<M>::new(columns);
+        self.group = Some(group);
+        self.unmark_scope(|scope| scope.group = false);
+        self
+    }
+    pub fn having<K: ColumnFilter<M, V, X>, V, X>(&mut self, key: K, value: V) -> &mut Self {
+        if let Some(having) = &mut self.having {
+            having.and_filter(key, value);
+        } else {
+            let mut having = crate::statements::having::Having::<M>::default();
+            having.and_filter(key, value);
+            self.having = Some(having);
+        }
+        self
+    }
+    pub fn having_sql<S: Into<crate::Sql>>(&mut self, sql: S) -> &mut Self {
+        if let Some(having) = &mut self.having {
+            having.and_filter_sql(sql);
+        } else {
+            let mut having = crate::statements::having::Having::<M>::default();
+            having.and_filter_sql(sql);
+            self.having = Some(having);
+        }
+        self
+    }
+    pub fn having_not<K: ColumnFilter<M, V, X>, V, X>(&mut self, key: K, value: V) -> &mut Self {
+        if let Some(having) = &mut self.having {
+            having.and_not_filter(key, value);
+        } else {
+            let mut having = crate::statements::having::Having::<M>::default();
+            having.and_not_filter(key, value);
+            self.having = Some(having);
+        }
+        self
+    }
+    pub fn having_or<K: ColumnFilter<M, V, X>, V, X>(&mut self, key: K, value: V) -> &mut Self {
+        if let Some(having) = &mut self.having {
+            having.or_filter(key, value);
+        } else {
+            let mut having = crate::statements::having::Having::<M>::default();
+            having.or_filter(key, value);
+            self.having = Some(having);
+        }
+        self
+    }
+    pub fn having_or_not<K: ColumnFilter<M, V, X>, V, X>(&mut self, key: K, value: V) -> &mut Self {
+        self.having.get_or_insert_with(Default::default).or_not_filter(key, value);
+        self
+    }
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// use arel::manager::SelectManager;
+    /// #[arel]
+    /// struct User {}
+    /// impl Arel for User {}
+    /// let mut select_manager = SelectManager::<User>::default();
+    /// select_manager.group(vec!["name"]).having_group(|g| g.having("a", 1).having_or("b", 2)).having_not_group(|g| g.having("c", 3));
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(
+    ///     select_manager.to_sql().unwrap().to_sql_string().unwrap(),
+    ///     r#"SELECT "user".* FROM "user" GROUP BY "user"."name" HAVING ("user"."a" = ? OR "user"."b" = ?) AND NOT ("user"."c" = ?)"#
+    /// );
+    /// ```
+    pub fn having_group<F>(&mut self, callback: F) -> &mut Self
+    where
+        F: FnOnce(&mut Self) -> &mut Self,
+    {
+        if let Some(filter) = Self::build_having_group(callback) {
+            self.having.get_or_insert_with(Default::default).and_filter_group(filter);
+        }
+        self
+    }
+    pub fn having_not_group<F>(&mut self, callback: F) -> &mut Self
+    where
+        F: FnOnce(&mut Self) -> &mut Self,
+    {
+        if let Some(filter) = Self::build_having_group(callback) {
+            self.having.get_or_insert_with(Default::default).and_not_filter_group(filter);
+        }
+        self
+    }
+    pub fn having_or_group<F>(&mut self, callback: F) -> &mut Self
+    where
+        F: FnOnce(&mut Self) -> &mut Self,
+    {
+        if let Some(filter) = Self::build_having_group(callback) {
+            self.having.get_or_insert_with(Default::default).or_filter_group(filter);
+        }
+        self
+    }
+    pub fn having_or_not_group<F>(&mut self, callback: F) -> &mut Self
+    where
+        F: FnOnce(&mut Self) -> &mut Self,
+    {
+        if let Some(filter) = Self::build_having_group(callback) {
+            self.having.get_or_insert_with(Default::default).or_not_filter_group(filter);
+        }
+        self
+    }
+    fn build_having_group<F>(callback: F) -> Option<crate::statements::filter::Filter<M>>
+    where
+        F: FnOnce(&mut Self) -> &mut Self,
+    {
+        let mut group = Self::default();
+        callback(&mut group);
+        group.having.map(|having| having.into_inner())
+    }
+    pub fn order<T: ColumnName<M>>(&mut self, column: T, sort_type: crate::SortConst) -> &mut Self {
+        self.order.get_or_insert_with(Default::default).append(column, sort_type);
+        self
+    }
+    pub fn order_asc<T: ColumnName<M>>(&mut self, column: T) -> &mut Self {
+        self.order(column, crate::SortConst::Asc)
+    }
+    pub fn order_desc<T: ColumnName<M>>(&mut self, column: T) -> &mut Self {
+        self.order(column, crate::SortConst::Desc)
+    }
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// use arel::statements::order::NullsConst;
+    /// #[arel]
+    /// struct User {}
+    /// impl Arel for User {}
+    /// let mut select_manager = User::query();
+    /// select_manager.order_nulls("age", arel::SortConst::Desc, NullsConst::First);
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(select_manager.to_sql().unwrap().to_sql_string().unwrap(), r#"SELECT "user".* FROM "user" ORDER BY "user"."age" IS NULL DESC, "user"."age" DESC"#);
+    /// #[cfg(feature = "postgres")]
+    /// assert_eq!(select_manager.to_sql().unwrap().to_sql_string().unwrap(), r#"SELECT "user".* FROM "user" ORDER BY "user"."age" DESC NULLS FIRST"#);
+    /// ```
+    pub fn order_nulls<T: ColumnName<M>>(&mut self, column: T, sort_type: crate::SortConst, nulls: crate::statements::order::NullsConst) -> &mut Self {
+        self.order.get_or_insert_with(Default::default).append_nulls(column, sort_type, nulls);
+        self
+    }
+    /// Order by a column of a joined table.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// #[arel]
+    /// struct User {}
+    /// impl Arel for User {}
+    /// #[arel]
+    /// struct Wallet {}
+    /// impl Arel for Wallet {}
+    /// let mut select_manager = User::query();
+    /// select_manager.join::<Wallet>(arel::JoinConst::InnerJoin).order_joined::<Wallet, _>("amount", arel::SortConst::Desc).order_asc("id");
+    /// #[cfg(not(feature = "mysql"))]
+    /// assert_eq!(
+    ///     select_manager.to_sql().unwrap().to_sql_string().unwrap(),
+    ///     r#"SELECT "user".* FROM "user" INNER JOIN "wallet" ON "user"."id" = "wallet"."user_id" ORDER BY "wallet"."amount" DESC, "user"."id" ASC"#
+    /// );
+    /// ```
+    pub fn order_joined<U: Arel, T: ColumnName<U>>(&mut self, column: T, sort_type: crate::SortConst) -> &mut Self {
+        self.order.get_or_insert_with(Default::default).append_joined::<U, T>(column, sort_type, None);
+        self
+    }
+    /// Order by an expression such as `CASE` or `FIELD(..)`, its binds are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// use arel::statements::expr::Expr;
+    /// #[arel]
+    /// struct User {
+    ///     #[arel(rename = "type")]
+    ///     r#type: String,
+    /// }
+    /// impl Arel for User {}
+    /// let mut select_manager = User::query();
+    /// select_manager.order_expr(Expr::case().when(Expr::from(User::columns().r#type).eq(Expr::value("ADMIN")), 0).otherwise(1), arel::SortConst::Asc).order_desc("id");
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(
+    ///     select_manager.to_sql().unwrap().to_sql_string().unwrap(),
+    ///     r#"SELECT "user".* FROM "user" ORDER BY CASE WHEN "user"."type" = ? THEN ? ELSE ? END ASC, "user"."id" DESC"#
+    /// );
+    /// ```
+    pub fn order_expr<E: Into<crate::statements::expr::Expr>>(&mut self, expr: E, sort_type: crate::SortConst) -> &mut Self {
+        self.order.get_or_insert_with(Default::default).append_expr(expr, sort_type, None);
+        self
+    }
+    pub fn order_sql<S: Into<crate::Sql>>(&mut self, sql: S) -> &mut Self {
+        self.order.get_or_insert_with(Default::default).append_sql(sql);
+        self
+    }
+    /// Replace every order given so far.
+    pub fn reorder<T: ColumnName<M>>(&mut self, column: T, sort_type: crate::SortConst) -> &mut Self {
+        self.unorder();
+        self.order(column, sort_type)
+    }
+    pub fn unorder(&mut self) -> &mut Self {
+        self.order = None;
+        self.unmark_scope(|scope| scope.order = 0);
+        self
+    }
+    /// Flip every order direction, a query without order is ordered by its primary keys descending.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// #[arel]
+    /// struct User {}
+    /// impl Arel for User {}
+    /// let mut select_manager = User::query();
+    /// select_manager.reverse_order();
+    /// #[cfg(not(feature = "mysql"))]
+    /// assert_eq!(select_manager.to_sql().unwrap().to_sql_string().unwrap(), r#"SELECT "user".* FROM "user" ORDER BY "user"."id" DESC"#);
+    ///
+    /// let mut select_manager = User::query();
+    /// select_manager.order_desc("created_at").order_asc("id").reverse_order();
+    /// #[cfg(not(feature = "mysql"))]
+    /// assert_eq!(select_manager.to_sql().unwrap().to_sql_string().unwrap(), r#"SELECT "user".* FROM "user" ORDER BY "user"."created_at" ASC, "user"."id" DESC"#);
+    /// ```
+    pub fn reverse_order(&mut self) -> &mut Self {
+        match &mut self.order {
+            Some(order) if !order.is_empty() => {
+                order.reverse();
+            }
+            _ => {
+                let columns = M::primary_keys().into_iter().map(|primary_key| (primary_key, crate::SortConst::Desc)).collect();
+                self.order = Some(crate::statements::order::Order::<M>::new_columns(columns));
+            }
+        }
+        self
+    }
+    pub fn limit(&mut self, num: usize) -> &mut Self {
+        let limit = crate::statements::limit::Limit::new(num);
+        self.limit = Some(limit);
+        self.unmark_scope(|scope| scope.limit = false);
+        self
+    }
+    pub fn offset(&mut self, num: usize) -> &mut Self {
+        let offset = crate::statements::offset::Offset::new(num);
+        self.offset = Some(offset);
+        self.unmark_scope(|scope| scope.offset = false);
+        self
+    }
+    pub fn paginate(&mut self, page: usize, page_size: usize) -> &mut Self {
+        let offset = (std::cmp::max(page, 1) - 1) * page_size;
+        self.limit(page_size);
+        self.offset(offset)
+    }
+    /// `FOR UPDATE`, the query fails to render on sqlite which has no row locks.
+    pub fn lock(&mut self) -> &mut Self {
+        self.lock_with_mode(crate::statements::lock::LockConst::Update)
+    }
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// #[arel]
+    /// struct User {}
+    /// impl Arel for User {}
+    /// #[arel]
+    /// struct Wallet {}
+    /// impl Arel for Wallet {}
+    /// let mut select_manager = User::query();
+    /// select_manager.join::<Wallet>(arel::JoinConst::InnerJoin).lock_for_share().lock_of("user").lock_nowait();
+    /// #[cfg(feature = "postgres")]
+    /// assert_eq!(
+    ///     select_manager.to_sql().unwrap().to_sql_string().unwrap(),
+    ///     r#"SELECT "user".* FROM "user" INNER JOIN "wallet" ON "user"."id" = "wallet"."user_id" FOR SHARE OF "user" NOWAIT"#
+    /// );
+    /// #[cfg(feature = "sqlite")]
+    /// assert!(select_manager.to_sql().is_err());
+    /// ```
+    pub fn lock_for_share(&mut self) -> &mut Self {
+        self.lock_with_mode(crate::statements::lock::LockConst::Share)
+    }
+    /// `FOR NO KEY UPDATE`, mysql takes `FOR UPDATE`.
+    pub fn lock_no_key_update(&mut self) -> &mut Self {
+        self.lock_with_mode(crate::statements::lock::LockConst::NoKeyUpdate)
+    }
+    pub fn lock_with_mode(&mut self, mode: crate::statements::lock::LockConst) -> &mut Self {
+        let lock = self.lock.take().unwrap_or_default().mode(mode);
+        self.lock = Some(lock);
+        self.unmark_scope(|scope| scope.lock = false);
+        self
+    }
+    /// Lock only the rows of `table` (`FOR UPDATE OF "table"` unless another mode is set), for queries with joins.
+    pub fn lock_of<T: ToString>(&mut self, table: T) -> &mut Self {
+        let lock = self.lock.take().unwrap_or_default().of(table);
+        self.lock = Some(lock);
+        self.unmark_scope(|scope| scope.lock = false);
+        self
+    }
+    /// `NOWAIT`, added to `FOR UPDATE` unless another mode is set.
+    pub fn lock_nowait(&mut self) -> &mut Self {
+        let lock = self.lock.take().unwrap_or_default().nowait();
+        self.lock = Some(lock);
+        self.unmark_scope(|scope| scope.lock = false);
+        self
+    }
+    /// `SKIP LOCKED`, added to `FOR UPDATE` unless another mode is set.
+    pub fn lock_skip_locked(&mut self) -> &mut Self {
+        let lock = self.lock.take().unwrap_or_default().skip_locked();
+        self.lock = Some(lock);
+        self.unmark_scope(|scope| scope.lock = false);
+        self
+    }
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// use arel::manager::SelectManager;
+    /// #[arel]
+    /// struct User {}
+    /// impl Arel for User {}
+    /// let mut select_manager = SelectManager::<User>::default();
+    /// select_manager.r#where("type", "ADMIN");
+    /// let mut compound_manager = select_manager.union(User::query().r#where("type", "USER"));
+    /// compound_manager.except(User::query().where_lt("age", 18));
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(
+    ///     compound_manager.to_sql().unwrap().to_sql_string().unwrap(),
+    ///     r#"SELECT * FROM (SELECT "user".* FROM "user" WHERE "user"."type" = ?) UNION SELECT * FROM (SELECT "user".* FROM "user" WHERE "user"."type" = ?) EXCEPT SELECT * FROM (SELECT "user".* FROM "user" WHERE "user"."age" < ?)"#
+    /// );
+    /// #[cfg(feature = "mysql")]
+    /// assert_eq!(
+    ///     compound_manager.to_sql().unwrap().to_sql_string().unwrap(),
+    ///     "(SELECT `user`.* FROM `user` WHERE `user`.`type` = ?) UNION (SELECT `user`.* FROM `user` WHERE `user`.`type` = ?) EXCEPT (SELECT `user`.* FROM `user` WHERE `user`.`age` < ?)"
+    /// );
+    /// ```
+    pub fn union(&self, query: &SelectManager<M>) -> crate::manager::CompoundManager<M> {
+        let mut compound_manager = crate::manager::CompoundManager::new(self);
+        compound_manager.union(query);
+        compound_manager
+    }
+    pub fn union_all(&self, query: &SelectManager<M>) -> crate::manager::CompoundManager<M> {
+        let mut compound_manager = crate::manager::CompoundManager::new(self);
+        compound_manager.union_all(query);
+        compound_manager
+    }
+    pub fn intersect(&self, query: &SelectManager<M>) -> crate::manager::CompoundManager<M> {
+        let mut compound_manager = crate::manager::CompoundManager::new(self);
+        compound_manager.intersect(query);
+        compound_manager
+    }
+    pub fn except(&self, query: &SelectManager<M>) -> crate::manager::CompoundManager<M> {
+        let mut compound_manager = crate::manager::CompoundManager::new(self);
+        compound_manager.except(query);
+        compound_manager
+    }
+    pub fn to_sql(&self) -> crate::Result<crate::Sql> {
+        self.to_sql_with_select(self.projection_sql()?, RenderOptions::default())
+    }
+    fn projection_sql(&self) -> crate::Result<crate::Sql> {
+        let table_name = M::table_name();
+        let mut select_sql = crate::Sql::new(format!("SELECT {} FROM {}", quote_column(&table_name, "*"), quote_ident(&table_name)));
+        if let Some(sql) = self.select.to_sql()? {
+            select_sql = sql;
+        }
+        Ok(select_sql)
+    }
+    /// The predicate picking the rows of this query for a set-based `UPDATE` or `DELETE`, `None` for every row.
+    ///
+    /// A plain where clause is used as is, otherwise the primary keys are matched against the query:
+    /// `("id") IN (SELECT * FROM (SELECT "user"."id" FROM .. JOIN .. LIMIT ..) AS "arel_sub")`,
+    /// the extra sub select lets mysql read from the table it changes.
+    pub(crate) fn to_target_filter_sql(&self) -> crate::Result<Option<crate::Sql>> {
+        let is_plain = self.with.is_none()
+            && self.join.is_none()
+            && self.group.is_none()
+            && self.having.is_none()
+            && self.limit.is_none()
+            && self.offset.is_none()
+            && !self.select.has_from();
+        if is_plain {
+            return match &self.r#where {
+                Some(r#where) => ArelStatement::to_sql(&**r#where),
+                None => Ok(None),
+            };
+        }
+        let table_name = M::table_name();
+        let primary_keys = M::primary_keys();
+        let select_sql = self.select.to_sql_with_sqls(primary_keys.iter().map(|primary_key| crate::Sql::new(quote_column(&table_name, primary_key))).collect());
+        let sub_sql = self.to_sql_with_select(select_sql, RenderOptions { skip_lock: true, ..Default::default() })?;
+        let mut final_sql = crate::Sql::new("(");
+        final_sql
+            .push_strs(primary_keys.iter().map(quote_ident).collect(), ", ")
+            .push_str(") IN (SELECT * FROM (")
+            .push_sql(sub_sql)
+            .push_str(format!(") AS {})", quote_ident("arel_sub")));
+        Ok(Some(final_sql))
+    }
+    // render every clause around `select_sql`, adjusted by `options`
+    fn to_sql_with_select(&self, select_sql: crate::Sql, options: RenderOptions) -> crate::Result<crate::Sql> {
+        let mut final_sql = crate::Sql::new("");
+
+        if let Some(with) = &self.with {
+            if let Some(sql) = with.to_sql()? {
+                final_sql.push_sql(sql).push_str(" ");
+            }
+        }
+
+        final_sql.push_sql(select_sql);
+
+        if let Some(join) = &self.join {
+            if let Some(sql) = join.to_sql()? {
+                final_sql.push_str(" ").push_sql(sql);
+            }
+        }
+
+        let filter_sql = match &self.r#where {
+            Some(r#where) => ArelStatement::to_sql(&**r#where)?,
+            None => None,
+        };
+        match (filter_sql, options.and_where) {
+            (Some(filter_sql), Some(and_where)) => {
+                final_sql.push_str(" WHERE (").push_sql(filter_sql).push_str(") AND (").push_sql(and_where).push_str(")");
+            }
+            (Some(filter_sql), None) => {
+                final_sql.push_str(" WHERE ").push_sql(filter_sql);
+            }
+            (None, Some(and_where)) => {
+                final_sql.push_str(" WHERE ").push_sql(and_where);
+            }
+            (None, None) => (),
+        }
+
+        if let Some(group) = &self.group {
+            if let Some(sql) = group.to_sql()? {
+                final_sql.push_str(" ").push_sql(sql);
+            }
+        }
+
+        if let Some(having) = &self.having {
+            if let Some(sql) = having.to_sql()? {
+                final_sql.push_str(" ").push_sql(sql);
+            }
+        }
+
+        if let Some(order_sql) = options.order {
+            final_sql.push_str(" ").push_sql(order_sql);
+        } else if !options.skip_order {
+            if let Some(order) = &self.order {
+                if let Some(sql) = order.to_sql()? {
+                    final_sql.push_str(" ").push_sql(sql);
+                }
+            }
+        }
+
+        if options.skip_limit || options.limit.is_some() || options.offset.is_some() {
+            // explicit LIMIT / OFFSET replace the ones of the query
+            if let Some(limit) = options.limit {
+                final_sql.push_str(format!(" LIMIT {}", limit));
+            } else if options.offset.is_some() {
+                Self::push_unbounded_limit(&mut final_sql);
+            }
+            if let Some(offset) = options.offset {
+                final_sql.push_str(format!(" OFFSET {}", offset));
+            }
+        } else {
+            if let Some(limit) = &self.limit {
+                if let Some(sql) = limit.to_sql()? {
+                    final_sql.push_str(" ").push_sql(sql);
+                }
+            } else if self.offset.is_some() {
+                Self::push_unbounded_limit(&mut final_sql);
+            }
+
+            if let Some(offset) = &self.offset {
+                if let Some(sql) = offset.to_sql()? {
+                    final_sql.push_str(" ").push_sql(sql);
+                }
+            }
+        }
+
+        if !options.skip_lock {
+            if let Some(lock) = &self.lock {
+                if let Some(sql) = lock.to_sql()? {
+                    final_sql.push_str(" ").push_sql(sql);
+                }
+            }
+        }
+
+        Ok(final_sql)
+    }
+    // sqlite and mysql take no OFFSET without a LIMIT
+    fn push_unbounded_limit(final_sql: &mut crate::Sql) {
+        if let Some(limit) = crate::db::dialect::compiled().unbounded_limit() {
+            final_sql.push_str(format!(" LIMIT {}", limit));
+        }
+    }
+    // grouped, limited or distinct rows have to be aggregated from a sub select
+    fn is_aggregate_wrapped(&self, unpaged: bool) -> bool {
+        self.group.is_some() || self.having.is_some() || self.select.is_distinct() || (!unpaged && (self.limit.is_some() || self.offset.is_some()))
+    }
+    // `SELECT {expr(column)} FROM ..`, `column` is qualified by the table or by the sub select alias,
+    // `unpaged` aggregates every row regardless of LIMIT and OFFSET
+    fn to_aggregate_sql<F>(&self, column: Option<&str>, expr: F, unpaged: bool) -> crate::Result<crate::Sql>
+    where
+        F: FnOnce(Option<String>) -> String,
+    {
+        if self.is_aggregate_wrapped(unpaged) {
+            let sub_sql = if unpaged {
+                self.to_sql_with_select(
+                    self.projection_sql()?,
+                    RenderOptions {
+                        skip_order: true,
+                        skip_limit: true,
+                        skip_lock: true,
+                        ..Default::default()
+                    },
+                )?
+            } else {
+                self.to_sql()?
+            };
+            let mut final_sql = crate::Sql::new(format!("SELECT {} FROM (", expr(column.map(|column| quote_column("arel_sub", column)))));
+            final_sql.push_sql(sub_sql).push_str(format!(") AS {}", quote_ident("arel_sub")));
+            Ok(final_sql)
+        } else {
+            let select_sql = self.select.to_sql_with_sqls(vec![crate::Sql::new(expr(column.map(|column| quote_column(M::table_name(), column))))]);
+            self.to_sql_with_select(
+                select_sql,
+                RenderOptions {
+                    skip_order: true,
+                    skip_limit: unpaged,
+                    skip_lock: true,
+                    ..Default::default()
+                },
+            )
+        }
+    }
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// use arel::manager::SelectManager;
+    /// #[arel]
+    /// struct User {}
+    /// impl Arel for User {}
+    /// let mut select_manager = SelectManager::<User>::default();
+    /// select_manager.select(vec!["name"]).where_gt("age", 18).order_desc("id");
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(select_manager.to_count_sql().unwrap().to_sql_string().unwrap(), r#"SELECT COUNT(*) FROM "user" WHERE "user"."age" > ?"#);
+    ///
+    /// select_manager.group(vec!["name"]);
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(
+    ///     select_manager.to_count_sql().unwrap().to_sql_string().unwrap(),
+    ///     r#"SELECT COUNT(*) FROM (SELECT "user"."name" FROM "user" WHERE "user"."age" > ? GROUP BY "user"."name" ORDER BY "user"."id" DESC) AS "arel_sub""#
+    /// );
+    /// // the caller's query is left untouched
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(select_manager.to_sql().unwrap().to_sql_string().unwrap(), r#"SELECT "user"."name" FROM "user" WHERE "user"."age" > ? GROUP BY "user"."name" ORDER BY "user"."id" DESC"#);
+    /// ```
+    pub fn to_count_sql(&self) -> crate::Result<crate::Sql> {
+        self.to_aggregate_sql(None, |_| "COUNT(*)".to_string(), false)
+    }
+    /// `SELECT {function}({column}) FROM ..`, e.g. `SUM`, `AVG`, `MIN`, `MAX`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// use arel::manager::SelectManager;
+    /// #[arel]
+    /// struct User {}
+    /// impl Arel for User {}
+    /// let mut select_manager = SelectManager::<User>::default();
+    /// #[cfg(not(feature = "mysql"))]
+    /// assert_eq!(select_manager.to_aggregate_function_sql("SUM", "age").unwrap().to_sql_string().unwrap(), r#"SELECT SUM("user"."age") FROM "user""#);
+    ///
+    /// select_manager.limit(10);
+    /// #[cfg(not(feature = "mysql"))]
+    /// assert_eq!(
+    ///     select_manager.to_aggregate_function_sql("MAX", "age").unwrap().to_sql_string().unwrap(),
+    ///     r#"SELECT MAX("arel_sub"."age") FROM (SELECT "user".* FROM "user" LIMIT 10) AS "arel_sub""#
+    /// );
+    /// ```
+    pub fn to_aggregate_function_sql<F: AsRef<str>, T: ColumnName<M>>(&self, function: F, column: T) -> crate::Result<crate::Sql> {
+        self.to_aggregate_sql(Some(column.column_name()), |column| format!("{}({})", function.as_ref(), column.unwrap_or_default()), false)
+    }
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// use arel::manager::SelectManager;
+    /// #[arel]
+    /// struct User {}
+    /// impl Arel for User {}
+    /// let mut select_manager = SelectManager::<User>::default();
+    /// #[cfg(not(feature = "mysql"))]
+    /// assert_eq!(select_manager.to_count_distinct_sql("name").unwrap().to_sql_string().unwrap(), r#"SELECT COUNT(DISTINCT "user"."name") FROM "user""#);
+    ///
+    /// select_manager.select(vec!["name"]).distinct();
+    /// #[cfg(not(feature = "mysql"))]
+    /// assert_eq!(
+    ///     select_manager.to_count_distinct_sql("name").unwrap().to_sql_string().unwrap(),
+    ///     r#"SELECT COUNT(DISTINCT "arel_sub"."name") FROM (SELECT DISTINCT "user"."name" FROM "user") AS "arel_sub""#
+    /// );
+    /// ```
+    pub fn to_count_distinct_sql<T: ColumnName<M>>(&self, column: T) -> crate::Result<crate::Sql> {
+        self.to_aggregate_sql(Some(column.column_name()), |column| format!("COUNT(DISTINCT {})", column.unwrap_or_default()), false)
+    }
+    /// `SELECT "table"."column" FROM ..`, keeps distinct, order, limit and offset.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// use arel::manager::SelectManager;
+    /// #[arel]
+    /// struct User {}
+    /// impl Arel for User {}
+    /// let mut select_manager = SelectManager::<User>::default();
+    /// select_manager.where_gt("age", 18).order_desc("id").limit(5);
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(select_manager.to_pluck_sql("name").unwrap().to_sql_string().unwrap(), r#"SELECT "user"."name" FROM "user" WHERE "user"."age" > ? ORDER BY "user"."id" DESC LIMIT 5"#);
+    /// ```
+    pub fn to_pluck_sql<T: ColumnName<M>>(&self, column: T) -> crate::Result<crate::Sql> {
+        let select_sql = self.select.to_sql_with_sqls(vec![crate::Sql::new(quote_column(M::table_name(), column.column_name()))]);
+        self.to_sql_with_select(select_sql, RenderOptions::default())
+    }
+    /// Keyset page query: the order columns (primary keys appended as tie breakers) compared with the cursor,
+    /// one extra row is fetched to detect a following page. LIMIT and OFFSET of the query are ignored.
+    ///
+    /// Orders with a nulls placement, expressions, raw sql or joined columns are an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// use arel::manager::{Cursor, SelectManager};
+    /// #[arel]
+    /// struct User {}
+    /// impl Arel for User {}
+    /// let mut select_manager = SelectManager::<User>::default();
+    /// select_manager.where_gt("age", 18).order_desc("created_at");
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(
+    ///     select_manager.to_keyset_sql(10, None).unwrap().to_sql_string().unwrap(),
+    ///     r#"SELECT "user".* FROM "user" WHERE "user"."age" > ? ORDER BY "user"."created_at" DESC, "user"."id" DESC LIMIT 11"#
+    /// );
+    ///
+    /// let cursor = Cursor::after(vec![arel::Value::from("2023-01-01"), arel::Value::from(100)]);
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(
+    ///     select_manager.to_keyset_sql(10, Some(&cursor)).unwrap().to_sql_string().unwrap(),
+    ///     r#"SELECT "user".* FROM "user" WHERE ("user"."age" > ?) AND (("user"."created_at" < ?) OR ("user"."created_at" = ? AND "user"."id" < ?)) ORDER BY "user"."created_at" DESC, "user"."id" DESC LIMIT 11"#
+    /// );
+    /// #[cfg(feature = "mysql")]
+    /// assert_eq!(
+    ///     select_manager.to_keyset_sql(10, Some(&cursor)).unwrap().to_sql_string().unwrap(),
+    ///     "SELECT `user`.* FROM `user` WHERE (`user`.`age` > ?) AND ((`user`.`created_at`, `user`.`id`) < (?, ?)) ORDER BY `user`.`created_at` DESC, `user`.`id` DESC LIMIT 11"
+    /// );
+    ///
+    /// let cursor = Cursor::before(vec![arel::Value::from("2023-01-01"), arel::Value::from(100)]);
+    /// #[cfg(feature = "postgres")]
+    /// assert_eq!(
+    ///     select_manager.to_keyset_sql(10, Some(&cursor)).unwrap().to_sql_string().unwrap(),
+    ///     r#"SELECT "user".* FROM "user" WHERE ("user"."age" > $1) AND (("user"."created_at", "user"."id") > ($2, $3)) ORDER BY "user"."created_at" ASC, "user"."id" ASC LIMIT 11"#
+    /// );
+    ///
+    /// // nulls placements and expressions have no keyset
+    /// assert!(User::query().order_nulls("age", arel::SortConst::Asc, arel::NullsConst::Last).to_keyset_sql(10, None).is_err());
+    /// assert!(User::query().order_expr(arel::Expr::raw("LENGTH(name)"), arel::SortConst::Asc).to_keyset_sql(10, None).is_err());
+    /// ```
+    pub fn to_keyset_sql(&self, per: usize, cursor: Option<&crate::manager::Cursor>) -> crate::Result<crate::Sql> {
+        let columns = self.keyset_columns()?;
+        let backward = cursor.map(|cursor| cursor.is_backward()).unwrap_or(false);
+        let and_where = match cursor {
+            Some(cursor) => {
+                if cursor.values().len() != columns.len() {
+                    return Err(crate::Error::Message(format!("cursor holds {} values, the keyset of {} has {} columns", cursor.values().len(), M::table_name(), columns.len())));
+                }
+                Some(Self::keyset_predicate(&columns, cursor.values(), backward))
+            }
+            None => None,
+        };
+        let table_name = M::table_name();
+        let order_sqls = columns
+            .iter()
+            .map(|(column, sort_type)| format!("{} {}", quote_column(&table_name, column), if backward { sort_type.reverse() } else { *sort_type }))
+            .collect::<Vec<String>>();
+        let mut order_sql = crate::Sql::new("ORDER BY ");
+        order_sql.push_strs(order_sqls, ", ");
+        self.to_sql_with_select(
+            self.projection_sql()?,
+            RenderOptions {
+                and_where,
+                order: Some(order_sql),
+                limit: Some(per + 1),
+                ..Default::default()
+            },
+        )
+    }
+    /// Batch query of [`SelectManager::find_in_batches`]: the next `batch_size` rows after the primary key values `after`,
+    /// in primary key order. The query's own ORDER BY, LIMIT and OFFSET are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// use arel::manager::SelectManager;
+    /// #[arel]
+    /// struct UserRole {
+    ///     #[arel(primary_key)]
+    ///     user_id: i64,
+    ///     #[arel(primary_key)]
+    ///     role_id: i64,
+    /// }
+    /// impl Arel for UserRole {}
+    /// let mut select_manager = SelectManager::<UserRole>::default();
+    /// select_manager.where_gt("user_id", 18).order_desc("role_id").limit(5);
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(
+    ///     select_manager.to_batch_sql(100, None).unwrap().to_sql_string().unwrap(),
+    ///     r#"SELECT "userrole".* FROM "userrole" WHERE "userrole"."user_id" > ? ORDER BY "userrole"."user_id" ASC, "userrole"."role_id" ASC LIMIT 100"#
+    /// );
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(
+    ///     select_manager.to_batch_sql(100, Some(&[arel::Value::from(20), arel::Value::from(3)])).unwrap().to_sql_string().unwrap(),
+    ///     r#"SELECT "userrole".* FROM "userrole" WHERE ("userrole"."user_id" > ?) AND (("userrole"."user_id" > ?) OR ("userrole"."user_id" = ? AND "userrole"."role_id" > ?)) ORDER BY "userrole"."user_id" ASC, "userrole"."role_id" ASC LIMIT 100"#
+    /// );
+    /// #[cfg(feature = "postgres")]
+    /// assert_eq!(
+    ///     select_manager.to_batch_sql(100, Some(&[arel::Value::from(20), arel::Value::from(3)])).unwrap().to_sql_string().unwrap(),
+    ///     r#"SELECT "userrole".* FROM "userrole" WHERE ("userrole"."user_id" > $1) AND (("userrole"."user_id", "userrole"."role_id") > ($2, $3)) ORDER BY "userrole"."user_id" ASC, "userrole"."role_id" ASC LIMIT 100"#
+    /// );
+    /// ```
+    pub fn to_batch_sql(&self, batch_size: usize, after: Option<&[crate::Value]>) -> crate::Result<crate::Sql> {
+        let table_name = M::table_name();
+        let columns = M::primary_keys().into_iter().map(|primary_key| (primary_key.to_string(), crate::SortConst::Asc)).collect::<Vec<_>>();
+        let and_where = match after {
+            Some(values) => {
+                if values.len() != columns.len() {
+                    return Err(crate::Error::Message(format!("{} primary key values given, {} has {} primary keys", values.len(), table_name, columns.len())));
+                }
+                Some(Self::keyset_predicate(&columns, values, false))
+            }
+            None => None,
+        };
+        let order_sqls = columns.iter().map(|(column, sort_type)| format!("{} {}", quote_column(&table_name, column), sort_type)).collect::<Vec<String>>();
+        let mut order_sql = crate::Sql::new("ORDER BY ");
+        order_sql.push_strs(order_sqls, ", ");
+        self.to_sql_with_select(
+            self.projection_sql()?,
+            RenderOptions {
+                and_where,
+                order: Some(order_sql),
+                skip_limit: true,
+                limit: Some(batch_size),
+                ..Default::default()
+            },
+        )
+    }
+    // the order columns followed by the primary keys missing from them
+    fn keyset_columns(&self) -> crate::Result<Vec<(String, crate::SortConst)>> {
+        let mut columns = vec![];
+        if let Some(order) = &self.order {
+            let order_columns = order.columns().ok_or_else(|| crate::Error::Message("keyset pagination requires plain column orders, not raw sql, expressions or joined columns".to_string()))?;
+            for (column, sort_type, nulls) in order_columns {
+                // `>` / `<` never match a null, rows past the nulls boundary would be skipped or repeated
+                if let Some(nulls) = nulls {
+                    return Err(crate::Error::Message(format!("keyset pagination can not order {}.{} {}, null values have no position to compare with", M::table_name(), column, nulls)));
+                }
+                columns.push((column.to_string(), sort_type));
+            }
+        }
+        let sort_type = columns.last().map(|(_, sort_type)| *sort_type).unwrap_or(crate::SortConst::Asc);
+        for primary_key in M::primary_keys() {
+            if !columns.iter().any(|(column, _)| column == primary_key) {
+                columns.push((primary_key.to_string(), sort_type));
+            }
+        }
+        Ok(columns)
+    }
+    // `(a, b) > (?, ?)`, or `(a > ?) OR (a = ? AND b > ?)` for mixed directions and on sqlite
+    fn keyset_predicate(columns: &[(String, crate::SortConst)], values: &[crate::Value], backward: bool) -> crate::Sql {
+        let table_name = M::table_name();
+        let operator = |sort_type: crate::SortConst| match (sort_type, backward) {
+            (crate::SortConst::Asc, false) | (crate::SortConst::Desc, true) => ">",
+            _ => "<",
+        };
+        let mut sql = crate::Sql::default();
+        let same_direction = columns.iter().all(|(_, sort_type)| *sort_type == columns[0].1);
+        if columns.len() > 1 && same_direction && crate::db::dialect::compiled().supports_row_values() {
+            let column_sqls = columns.iter().map(|(column, _)| quote_column(&table_name, column)).collect();
+            sql.push_str("(")
+                .push_strs(column_sqls, ", ")
+                .push_str(format!(") {} (", operator(columns[0].1)))
+                .push_binds(values.to_vec(), ", ")
+                .push_str(")");
+        } else {
+            let wrap = columns.len() > 1;
+            let disjunctions = (0..columns.len())
+                .map(|idx| {
+                    let mut disjunction = crate::Sql::new(if wrap { "(" } else { "" });
+                    for (prefix_idx, (column, _)) in columns[..idx].iter().enumerate() {
+                        disjunction.push_str(format!("{} = ", quote_column(&table_name, column))).push_bind(values[prefix_idx].clone()).push_str(" AND ");
+                    }
+                    let (column, sort_type) = &columns[idx];
+                    disjunction.push_str(format!("{} {} ", quote_column(&table_name, column), operator(*sort_type))).push_bind(values[idx].clone());
+                    disjunction.push_str(if wrap { ")" } else { "" });
+                    disjunction
+                })
+                .collect::<Vec<crate::Sql>>();
+            sql.push_sqls(disjunctions, " OR ");
+        }
+        sql
+    }
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// use arel::manager::SelectManager;
+    /// #[arel]
+    /// struct User {}
+    /// impl Arel for User {}
+    /// let mut select_manager = SelectManager::<User>::default();
+    /// select_manager.where_gt("age", 18).order_desc("id").limit(5);
+    /// let (count_sql, data_sql) = select_manager.to_page_sqls(3, 20).unwrap();
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(count_sql.to_sql_string().unwrap(), r#"SELECT COUNT(*) FROM "user" WHERE "user"."age" > ?"#);
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(data_sql.to_sql_string().unwrap(), r#"SELECT "user".* FROM "user" WHERE "user"."age" > ? ORDER BY "user"."id" DESC LIMIT 20 OFFSET 40"#);
+    /// ```
+    pub fn to_page_sqls(&self, page: usize, per: usize) -> crate::Result<(crate::Sql, crate::Sql)> {
+        let count_sql = self.to_aggregate_sql(None, |_| "COUNT(*)".to_string(), true)?;
+        let data_sql = self.to_sql_with_select(
+            self.projection_sql()?,
+            RenderOptions {
+                limit: Some(per),
+                offset: Some((std::cmp::max(page, 1) - 1) * per),
+                ..Default::default()
+            },
+        )?;
+        Ok((count_sql, data_sql))
+    }
+    /// `SELECT {group columns}, {aggregate} FROM .. GROUP BY ..`, keeps having, order, limit and offset.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// use arel::manager::SelectManager;
+    /// #[arel]
+    /// struct User {}
+    /// impl Arel for User {}
+    /// let mut select_manager = SelectManager::<User>::default();
+    /// select_manager.group(vec!["type", "gender"]).order_asc("type");
+    /// #[cfg(not(feature = "mysql"))]
+    /// assert_eq!(
+    ///     select_manager.to_group_aggregate_sql("COUNT(*)").unwrap().to_sql_string().unwrap(),
+    ///     r#"SELECT "user"."type", "user"."gender", COUNT(*) FROM "user" GROUP BY "user"."type", "user"."gender" ORDER BY "user"."type" ASC"#
+    /// );
+    ///
+    /// assert!(SelectManager::<User>::default().to_group_aggregate_sql("COUNT(*)").is_err());
+    /// ```
+    pub fn to_group_aggregate_sql<S: Into<crate::Sql>>(&self, aggregate: S) -> crate::Result<crate::Sql> {
+        let group = self.group.as_ref().ok_or_else(|| crate::Error::Message(format!("grouped aggregate on {} requires group()", M::table_name())))?;
+        let mut sqls = group.sqls().to_vec();
+        sqls.push(aggregate.into());
+        let select_sql = self.select.to_sql_with_sqls(sqls);
+        self.to_sql_with_select(select_sql, RenderOptions { skip_lock: true, ..Default::default() })
+    }
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// use arel::manager::SelectManager;
+    /// #[arel]
+    /// struct User {}
+    /// impl Arel for User {}
+    /// let mut select_manager = SelectManager::<User>::default();
+    /// select_manager.where_gt("age", 18).order_desc("id");
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(select_manager.to_exists_sql().unwrap().to_sql_string().unwrap(), r#"SELECT 1 FROM "user" WHERE "user"."age" > ? LIMIT 1"#);
+    ///
+    /// select_manager.offset(10);
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(
+    ///     select_manager.to_exists_sql().unwrap().to_sql_string().unwrap(),
+    ///     r#"SELECT 1 FROM (SELECT "user".* FROM "user" WHERE "user"."age" > ? ORDER BY "user"."id" DESC LIMIT -1 OFFSET 10) AS "arel_sub" LIMIT 1"#
+    /// );
+    /// #[cfg(feature = "mysql")]
+    /// assert_eq!(
+    ///     select_manager.to_exists_sql().unwrap().to_sql_string().unwrap(),
+    ///     "SELECT 1 FROM (SELECT `user`.* FROM `user` WHERE `user`.`age` > ? ORDER BY `user`.`id` DESC LIMIT 18446744073709551615 OFFSET 10) AS `arel_sub` LIMIT 1"
+    /// );
+    /// ```
+    pub fn to_exists_sql(&self) -> crate::Result<crate::Sql> {
+        let mut final_sql = self.to_aggregate_sql(None, |_| "1".to_string(), false)?;
+        final_sql.push_str(" LIMIT 1");
+        Ok(final_sql)
+    }
+}
+
+impl<M: Arel> SelectManager<M>
+where
+    for<'b> M: Send + Unpin + sqlx::FromRow<'b, crate::db::DatabaseRow>,
+{
+    pub fn to_query_builder<'a>(&self) -> crate::Result<crate::sql::QueryBuilder<'a>> {
+        let sql = self.to_sql()?;
+        Ok(sql.try_into()?)
+    }
+    pub async fn fetch_count_with_exec<'a, E>(&self, executor: E) -> crate::Result<i64>
+    where
+        E: sqlx::Executor<'a, Database = crate::db::Database>,
+    {
+        let row: (i64,) = self.to_sql()?.fetch_one_as_with_exec(executor).await?;
+        Ok(row.0)
+    }
+    pub async fn fetch_count(&self) -> crate::Result<i64> {
+        self.fetch_count_with_exec(M::pool()?).await
+    }
+    pub(crate) async fn fetch_one_as_with_exec<'a, T, E>(&self, executor: E) -> crate::Result<T>
+    where
+        for<'b> T: Send + Unpin + sqlx::FromRow<'b, crate::db::DatabaseRow>,
+        E: sqlx::Executor<'a, Database = crate::db::Database>,
+    {
+        let ret: T = self.to_sql()?.fetch_one_as_with_exec(executor).await?;
+        Ok(ret)
+    }
+    pub(crate) async fn fetch_one_optional_as_with_exec<'a, T, E>(&self, executor: E) -> crate::Result<Option<T>>
+    where
+        for<'b> T: Send + Unpin + sqlx::FromRow<'b, crate::db::DatabaseRow>,
+        E: sqlx::Executor<'a, Database = crate::db::Database>,
     {
         let ret: Option<T> = self.to_sql()?.fetch_one_optional_as_with_exec(executor).await?;
         Ok(ret)
@@ -403,4 +1881,311 @@ where
     pub async fn fetch_all(&self) -> crate::Result<Vec<M>> {
         self.fetch_all_as().await
     }
+    /// Rows decoded one at a time as the stream is polled, instead of buffering the whole result set.
+    ///
+    /// Errors, including a query that fails to render, are yielded as stream items.
+    pub fn fetch_stream_with_exec<'a, E>(&self, executor: E) -> futures_core::stream::BoxStream<'a, crate::Result<M>>
+    where
+        M: 'a,
+        E: sqlx::Executor<'a, Database = crate::db::Database> + 'a,
+    {
+        match self.to_sql() {
+            Ok(sql) => sql.fetch_stream_as_with_exec(executor),
+            Err(err) => Box::pin(futures_util::stream::once(futures_util::future::ready(Err(err)))),
+        }
+    }
+    pub fn fetch_stream(&self) -> futures_core::stream::BoxStream<'static, crate::Result<M>>
+    where
+        M: 'static,
+    {
+        match M::pool() {
+            Ok(pool) => self.fetch_stream_with_exec(pool),
+            Err(err) => Box::pin(futures_util::stream::once(futures_util::future::ready(Err(err)))),
+        }
+    }
+    /// `COUNT(*)` of the rows this query returns, grouped or limited queries are counted from a sub select.
+    pub async fn count(&self) -> crate::Result<i64> {
+        let row: (i64,) = self.to_count_sql()?.fetch_one_as_with_exec(M::pool()?).await?;
+        Ok(row.0)
+    }
+    pub async fn count_distinct<T: ColumnName<M>>(&self, column: T) -> crate::Result<i64> {
+        let row: (i64,) = self.to_count_distinct_sql(column)?.fetch_one_as_with_exec(M::pool()?).await?;
+        Ok(row.0)
+    }
+    /// `None` when no row matches.
+    pub async fn sum<V, T: ColumnName<M>>(&self, column: T) -> crate::Result<Option<V>>
+    where
+        for<'b> V: Send + Unpin + sqlx::Decode<'b, crate::db::Database> + sqlx::Type<crate::db::Database>,
+    {
+        self.fetch_aggregate("SUM", column).await
+    }
+    pub async fn avg<V, T: ColumnName<M>>(&self, column: T) -> crate::Result<Option<V>>
+    where
+        for<'b> V: Send + Unpin + sqlx::Decode<'b, crate::db::Database> + sqlx::Type<crate::db::Database>,
+    {
+        self.fetch_aggregate("AVG", column).await
+    }
+    pub async fn min<V, T: ColumnName<M>>(&self, column: T) -> crate::Result<Option<V>>
+    where
+        for<'b> V: Send + Unpin + sqlx::Decode<'b, crate::db::Database> + sqlx::Type<crate::db::Database>,
+    {
+        self.fetch_aggregate("MIN", column).await
+    }
+    pub async fn max<V, T: ColumnName<M>>(&self, column: T) -> crate::Result<Option<V>>
+    where
+        for<'b> V: Send + Unpin + sqlx::Decode<'b, crate::db::Database> + sqlx::Type<crate::db::Database>,
+    {
+        self.fetch_aggregate("MAX", column).await
+    }
+    async fn fetch_aggregate<V, T: ColumnName<M>>(&self, function: &str, column: T) -> crate::Result<Option<V>>
+    where
+        for<'b> V: Send + Unpin + sqlx::Decode<'b, crate::db::Database> + sqlx::Type<crate::db::Database>,
+    {
+        let row: (Option<V>,) = self.to_aggregate_function_sql(function, column)?.fetch_one_as_with_exec(M::pool()?).await?;
+        Ok(row.0)
+    }
+    pub async fn pluck<V, T: ColumnName<M>>(&self, column: T) -> crate::Result<Vec<V>>
+    where
+        for<'b> V: Send + Unpin + sqlx::Decode<'b, crate::db::Database> + sqlx::Type<crate::db::Database>,
+    {
+        let rows: Vec<(V,)> = self.to_pluck_sql(column)?.fetch_all_as_with_exec(M::pool()?).await?;
+        Ok(rows.into_iter().map(|row| row.0).collect())
+    }
+    /// The column of the first row.
+    pub async fn pick<V, T: ColumnName<M>>(&self, column: T) -> crate::Result<Option<V>>
+    where
+        for<'b> V: Send + Unpin + sqlx::Decode<'b, crate::db::Database> + sqlx::Type<crate::db::Database>,
+    {
+        let mut final_sql = self.to_pluck_sql(column)?;
+        if self.limit.is_some() || self.offset.is_some() {
+            let pluck_sql = final_sql;
+            final_sql = crate::Sql::new("SELECT * FROM (");
+            final_sql.push_sql(pluck_sql).push_str(format!(") AS {}", quote_ident("arel_sub")));
+        }
+        final_sql.push_str(" LIMIT 1");
+        let row: Option<(V,)> = final_sql.fetch_one_optional_as_with_exec(M::pool()?).await?;
+        Ok(row.map(|row| row.0))
+    }
+    /// Primary key values, models with a composite primary key should `pluck` each key instead.
+    pub async fn ids<V>(&self) -> crate::Result<Vec<V>>
+    where
+        for<'b> V: Send + Unpin + sqlx::Decode<'b, crate::db::Database> + sqlx::Type<crate::db::Database>,
+    {
+        match M::primary_keys()[..] {
+            [primary_key] => self.pluck(primary_key).await,
+            _ => Err(crate::Error::Message(format!("ids() requires a single primary key on {}", M::table_name()))),
+        }
+    }
+    /// Fetch page `page` (starts from 1) of `per` rows together with the total count, see [`SelectManager::to_page_sqls`].
+    ///
+    /// Both queries run over one connection.
+    pub async fn fetch_page(&self, page: usize, per: usize) -> crate::Result<crate::manager::Page<M>> {
+        self.fetch_page_with_acquire(page, per, M::pool()?).await
+    }
+    /// [`SelectManager::fetch_page`] with both queries run in `tx`.
+    pub async fn fetch_page_with_tx(&self, page: usize, per: usize, tx: &mut sqlx::Transaction<'_, crate::db::Database>) -> crate::Result<crate::manager::Page<M>> {
+        self.fetch_page_with_conn(page, per, tx.as_mut()).await
+    }
+    /// [`SelectManager::fetch_page`] over one connection of `acquire`, a pool, connection or transaction.
+    pub async fn fetch_page_with_acquire<'a, A>(&self, page: usize, per: usize, acquire: A) -> crate::Result<crate::manager::Page<M>>
+    where
+        A: sqlx::Acquire<'a, Database = crate::db::Database> + Send,
+    {
+        let mut conn = acquire.acquire().await?;
+        self.fetch_page_with_conn(page, per, &mut conn).await
+    }
+    async fn fetch_page_with_conn(&self, page: usize, per: usize, conn: &mut crate::db::DatabaseConnection) -> crate::Result<crate::manager::Page<M>> {
+        let (count_sql, data_sql) = self.to_page_sqls(page, per)?;
+        let total: (i64,) = count_sql.fetch_one_as_with_exec(&mut *conn).await?;
+        let items: Vec<M> = data_sql.fetch_all_as_with_exec(&mut *conn).await?;
+        Ok(crate::manager::Page::new(items, total.0, page, per))
+    }
+    /// Fetch `per` rows after (or before) `cursor`, ordered by the query's order columns with the primary keys as tie breakers.
+    ///
+    /// The order columns must not be null and have to be part of the projection, nulls placements and expressions are rejected.
+    pub async fn fetch_keyset_page(&self, per: usize, cursor: Option<&crate::manager::Cursor>) -> crate::Result<crate::manager::KeysetPage<M>> {
+        let columns = self.keyset_columns()?;
+        let mut items: Vec<M> = self.to_keyset_sql(per, cursor)?.fetch_all_as_with_exec(M::pool()?).await?;
+        let has_more = items.len() > per;
+        items.truncate(per);
+        let backward = cursor.map(|cursor| cursor.is_backward()).unwrap_or(false);
+        if backward {
+            items.reverse();
+        }
+        let (has_next, has_prev) = if backward { (true, has_more) } else { (has_more, cursor.is_some()) };
+        let cursor_values = |item: &M| {
+            columns
+                .iter()
+                .map(|(column, _)| item.attribute_value(column).ok_or_else(|| crate::Error::Message(format!("keyset column {}.{} is not loaded", M::table_name(), column))))
+                .collect::<crate::Result<Vec<crate::Value>>>()
+        };
+        let next_cursor = match items.last() {
+            Some(item) if has_next => Some(crate::manager::Cursor::after(cursor_values(item)?)),
+            _ => None,
+        };
+        let prev_cursor = match items.first() {
+            Some(item) if has_prev => Some(crate::manager::Cursor::before(cursor_values(item)?)),
+            _ => None,
+        };
+        Ok(crate::manager::KeysetPage { items, next_cursor, prev_cursor })
+    }
+    /// Walk the matching rows in primary key order, `batch_size` rows at a time, without OFFSET scans.
+    ///
+    /// Every batch is fetched after the primary key values of the previous one, so the primary keys have to be part of the projection.
+    /// Stops at the first error returned by `callback`.
+    pub async fn find_in_batches<F, Fut>(&self, batch_size: usize, mut callback: F) -> crate::Result<()>
+    where
+        F: FnMut(Vec<M>) -> Fut,
+        Fut: Future<Output = crate::Result<()>>,
+    {
+        let mut position = BatchPosition::Start;
+        while let Some(batch) = self.next_batch(batch_size, &mut position).await? {
+            callback(batch).await?;
+        }
+        Ok(())
+    }
+    /// [`SelectManager::find_in_batches`] handing over one row at a time.
+    pub async fn find_each<F, Fut>(&self, batch_size: usize, mut callback: F) -> crate::Result<()>
+    where
+        F: FnMut(M) -> Fut,
+        Fut: Future<Output = crate::Result<()>>,
+    {
+        let mut position = BatchPosition::Start;
+        while let Some(batch) = self.next_batch(batch_size, &mut position).await? {
+            for item in batch {
+                callback(item).await?;
+            }
+        }
+        Ok(())
+    }
+    async fn next_batch(&self, batch_size: usize, position: &mut BatchPosition) -> crate::Result<Option<Vec<M>>> {
+        if batch_size == 0 {
+            return Err(crate::Error::Message("batch_size must be greater than 0".to_string()));
+        }
+        let after = match position {
+            BatchPosition::Start => None,
+            BatchPosition::After(values) => Some(&values[..]),
+            BatchPosition::Done => return Ok(None),
+        };
+        let batch: Vec<M> = self.to_batch_sql(batch_size, after)?.fetch_all_as_with_exec(M::pool()?).await?;
+        *position = match batch.last() {
+            Some(item) if batch.len() == batch_size => BatchPosition::After(
+                M::primary_keys()
+                    .into_iter()
+                    .map(|primary_key| item.attribute_value(primary_key).ok_or_else(|| crate::Error::Message(format!("primary key {}.{} is not loaded", M::table_name(), primary_key))))
+                    .collect::<crate::Result<Vec<crate::Value>>>()?,
+            ),
+            _ => BatchPosition::Done,
+        };
+        Ok(if batch.is_empty() { None } else { Some(batch) })
+    }
+    /// `{group value: COUNT(*)}` of a query grouped by one column, errors for any other number of group columns.
+    pub async fn group_count<K>(&self) -> crate::Result<HashMap<K, i64>>
+    where
+        for<'b> K: Send + Unpin + Eq + Hash + sqlx::Decode<'b, crate::db::Database> + sqlx::Type<crate::db::Database>,
+    {
+        self.fetch_group_aggregate(crate::Sql::new("COUNT(*)")).await
+    }
+    /// `[(group values, COUNT(*))]` of a query grouped by several columns, `K` is a tuple like `(String, i32)`.
+    pub async fn group_count_vec<K>(&self) -> crate::Result<Vec<(K, i64)>>
+    where
+        for<'b> K: Send + Unpin + sqlx::FromRow<'b, crate::db::DatabaseRow>,
+    {
+        self.fetch_group_aggregate_vec(crate::Sql::new("COUNT(*)")).await
+    }
+    /// `{group value: SUM(column)}` of a query grouped by one column, errors for any other number of group columns.
+    pub async fn group_sum<K, V, T: ColumnName<M>>(&self, column: T) -> crate::Result<HashMap<K, V>>
+    where
+        for<'b> K: Send + Unpin + Eq + Hash + sqlx::Decode<'b, crate::db::Database> + sqlx::Type<crate::db::Database>,
+        for<'b> V: Send + Unpin + sqlx::Decode<'b, crate::db::Database> + sqlx::Type<crate::db::Database>,
+    {
+        self.fetch_group_aggregate(crate::Sql::new(format!("SUM({})", quote_column(M::table_name(), column.column_name())))).await
+    }
+    /// `[(group values, SUM(column))]` of a query grouped by several columns.
+    pub async fn group_sum_vec<K, V, T: ColumnName<M>>(&self, column: T) -> crate::Result<Vec<(K, V)>>
+    where
+        for<'b> K: Send + Unpin + sqlx::FromRow<'b, crate::db::DatabaseRow>,
+        for<'b> V: Send + Unpin + sqlx::Decode<'b, crate::db::Database> + sqlx::Type<crate::db::Database>,
+    {
+        self.fetch_group_aggregate_vec(crate::Sql::new(format!("SUM({})", quote_column(M::table_name(), column.column_name())))).await
+    }
+    async fn fetch_group_aggregate<K, V>(&self, aggregate: crate::Sql) -> crate::Result<HashMap<K, V>>
+    where
+        for<'b> K: Send + Unpin + Eq + Hash + sqlx::Decode<'b, crate::db::Database> + sqlx::Type<crate::db::Database>,
+        for<'b> V: Send + Unpin + sqlx::Decode<'b, crate::db::Database> + sqlx::Type<crate::db::Database>,
+    {
+        // K decodes column 0 and V column 1, a second group column would be read as the aggregate
+        if let Some(group) = &self.group {
+            if group.sqls().len() != 1 {
+                return Err(crate::Error::Message(format!(
+                    "group_count / group_sum on {} need exactly one group column, got {}, use group_count_vec / group_sum_vec",
+                    M::table_name(),
+                    group.sqls().len()
+                )));
+            }
+        }
+        let rows: Vec<(K, V)> = self.to_group_aggregate_sql(aggregate)?.fetch_all_as_with_exec(M::pool()?).await?;
+        Ok(rows.into_iter().collect())
+    }
+    async fn fetch_group_aggregate_vec<K, V>(&self, aggregate: crate::Sql) -> crate::Result<Vec<(K, V)>>
+    where
+        for<'b> K: Send + Unpin + sqlx::FromRow<'b, crate::db::DatabaseRow>,
+        for<'b> V: Send + Unpin + sqlx::Decode<'b, crate::db::Database> + sqlx::Type<crate::db::Database>,
+    {
+        let sql = self.to_group_aggregate_sql(aggregate)?;
+        // the aggregate follows the group columns, the key only reads the leading columns
+        let value_index = self.group.as_ref().map(|group| group.sqls().len()).unwrap_or_default();
+        let rows = sql.fetch_all_with_exec(M::pool()?).await?;
+        rows.iter()
+            .map(|row| Ok((K::from_row(row)?, row.try_get::<V, _>(value_index)?)))
+            .collect::<Result<Vec<_>, sqlx::Error>>()
+            .map_err(Into::into)
+    }
+    pub async fn exists(&self) -> crate::Result<bool> {
+        let row: Option<()> = self.to_exists_sql()?.fetch_one_optional_as_with_exec(M::pool()?).await?;
+        Ok(row.is_some())
+    }
+    /// `UPDATE .. SET` every row this query matches, returns the rows affected.
+    pub async fn update_all<K: ColumnName<M>, E: crate::statements::expr::AssignValue>(&self, assignments: Vec<(K, E)>) -> crate::Result<u64> {
+        self.to_update_manager(assignments).execute().await
+    }
+    /// [`SelectManager::update_all`] reading the updated rows back with `RETURNING *` (sqlite | postgres).
+    pub async fn update_all_returning<K: ColumnName<M>, E: crate::statements::expr::AssignValue>(&self, assignments: Vec<(K, E)>) -> crate::Result<Vec<M>> {
+        self.to_update_manager(assignments).fetch_all().await
+    }
+    /// `DELETE` every row this query matches in one statement, without callbacks, returns the rows affected.
+    pub async fn delete_all(&self) -> crate::Result<u64> {
+        self.delete_manager().execute().await
+    }
+    /// Load every row this query matches and [`Arel::destroy_with_tx`] them one by one, callbacks included.
+    pub async fn destroy_all_with_tx(&self, tx: &mut sqlx::Transaction<'_, crate::db::Database>) -> crate::Result<Vec<M>> {
+        let mut models: Vec<M> = self.fetch_all_with_exec(tx.as_mut()).await?;
+        for model in models.iter_mut() {
+            model.destroy_with_tx(tx).await?;
+        }
+        Ok(models)
+    }
+    /// [`SelectManager::destroy_all_with_tx`] in one transaction, `after_destroy_commit` runs once it is committed.
+    pub async fn destroy_all(&self) -> crate::Result<Vec<M>> {
+        let mut tx = M::pool()?.begin().await?;
+        let mut models = match self.destroy_all_with_tx(&mut tx).await {
+            Ok(models) => models,
+            Err(e) => {
+                tx.rollback().await?;
+                return Err(e);
+            }
+        };
+        tx.commit().await?;
+        for model in models.iter_mut() {
+            model.after_destroy_commit().await?;
+        }
+        Ok(models)
+    }
+    fn to_update_manager<K: ColumnName<M>, E: crate::statements::expr::AssignValue>(&self, assignments: Vec<(K, E)>) -> crate::manager::UpdateManager<M> {
+        let mut update_manager = self.update_manager();
+        for (column, expr) in assignments {
+            update_manager.set(column, expr);
+        }
+        update_manager
+    }
 }
@@ -0,0 +1,126 @@
+use super::SelectManager;
+use crate::prelude::*;
+use crate::sql::quote_ident;
+use crate::statements::column::ColumnName;
+use std::marker::PhantomData;
+
+/// Set-based `UPDATE` of the rows a [`SelectManager`] matches, created by [`SelectManager::update_manager`].
+///
+/// The where clause of the query is reused as is, queries with joins, grouping or LIMIT/OFFSET
+/// update the rows whose primary keys the query returns.
+///
+/// # Examples
+///
+/// ```
+/// use arel::prelude::*;
+/// #[arel]
+/// struct User {}
+/// impl Arel for User {}
+/// #[arel]
+/// struct Wallet {}
+/// impl Arel for Wallet {}
+/// let mut update_manager = User::query().join::<Wallet>(arel::JoinConst::InnerJoin).where_sql(r#""wallet"."amount" > 100"#).order_desc("id").limit(10).update_manager();
+/// update_manager.set("type", "VIP");
+/// #[cfg(feature = "sqlite")]
+/// assert_eq!(
+///     update_manager.to_returning_sql().unwrap().to_sql_string().unwrap(),
+///     r#"UPDATE "user" SET "type" = ? WHERE ("id") IN (SELECT * FROM (SELECT "user"."id" FROM "user" INNER JOIN "wallet" ON "user"."id" = "wallet"."user_id" WHERE "wallet"."amount" > 100 ORDER BY "user"."id" DESC LIMIT 10) AS "arel_sub") RETURNING *"#
+/// );
+/// #[cfg(feature = "mysql")]
+/// assert!(update_manager.to_returning_sql().is_err());
+/// ```
+#[derive(Debug)]
+pub struct UpdateManager<M: crate::Arel> {
+    target: Option<crate::Sql>,
+    error: Option<String>,
+    assignments: Vec<crate::Sql>,
+    _marker: PhantomData<M>,
+}
+
+impl<M: Arel> Clone for UpdateManager<M> {
+    fn clone(&self) -> Self {
+        Self {
+            target: self.target.clone(),
+            error: self.error.clone(),
+            assignments: self.assignments.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<M: Arel> UpdateManager<M> {
+    pub fn new(query: &SelectManager<M>) -> Self {
+        let (target, error) = match query.to_target_filter_sql() {
+            Ok(target) => (target, None),
+            Err(err) => (None, Some(err.to_string())),
+        };
+        Self {
+            target,
+            error,
+            assignments: vec![],
+            _marker: PhantomData,
+        }
+    }
+    /// `"column" = expr`, plain values are bound, e.g. `set("age", Expr::from(User::columns().age) + 1)`.
+    pub fn set<K: ColumnName<M>, E: crate::statements::expr::AssignValue>(&mut self, column: K, expr: E) -> &mut Self {
+        let mut sql = crate::Sql::new(format!("{} = ", quote_ident(column.column_name())));
+        sql.push_sql(expr.into_expr().into_sql());
+        self.assignments.push(sql);
+        self
+    }
+    pub fn set_sql<S: Into<crate::Sql>>(&mut self, sql: S) -> &mut Self {
+        self.assignments.push(sql.into());
+        self
+    }
+    pub fn to_sql(&self) -> crate::Result<crate::Sql> {
+        if let Some(message) = &self.error {
+            return Err(crate::Error::Message(message.clone()));
+        }
+        if self.assignments.is_empty() {
+            return Err(crate::Error::Message(format!("update of {} without assignments", M::table_name())));
+        }
+        let mut final_sql = crate::Sql::new(format!("UPDATE {} SET ", quote_ident(M::table_name())));
+        final_sql.push_sqls(self.assignments.clone(), ", ");
+        if let Some(target) = &self.target {
+            final_sql.push_str(" WHERE ").push_sql(target.clone());
+        }
+        Ok(final_sql)
+    }
+    /// [`UpdateManager::to_sql`] followed by `RETURNING *`, which mysql does not support.
+    pub fn to_returning_sql(&self) -> crate::Result<crate::Sql> {
+        let dialect = crate::db::dialect::compiled();
+        if !dialect.supports_returning() {
+            return Err(crate::Error::Message(format!("{} does not support UPDATE .. RETURNING", dialect.name().to_lowercase())));
+        }
+        let mut final_sql = self.to_sql()?;
+        final_sql.push_str(" RETURNING *");
+        Ok(final_sql)
+    }
+    /// Rows affected.
+    pub async fn execute_with_exec<'a, E>(&self, executor: E) -> crate::Result<u64>
+    where
+        E: sqlx::Executor<'a, Database = crate::db::Database>,
+    {
+        let result = self.to_sql()?.exec(executor).await?;
+        Ok(result.rows_affected())
+    }
+    pub async fn execute(&self) -> crate::Result<u64> {
+        self.execute_with_exec(M::pool()?).await
+    }
+}
+
+impl<M: Arel> UpdateManager<M>
+where
+    for<'b> M: Send + Unpin + sqlx::FromRow<'b, crate::db::DatabaseRow>,
+{
+    /// The updated rows, read back with `RETURNING *`.
+    pub async fn fetch_all_with_exec<'a, E>(&self, executor: E) -> crate::Result<Vec<M>>
+    where
+        E: sqlx::Executor<'a, Database = crate::db::Database>,
+    {
+        self.to_returning_sql()?.fetch_all_as_with_exec(executor).await
+    }
+    pub async fn fetch_all(&self) -> crate::Result<Vec<M>> {
+        self.fetch_all_with_exec(M::pool()?).await
+    }
+}
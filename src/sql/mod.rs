@@ -1,6 +1,8 @@
 mod query_builder;
+mod quote;
 
 pub use query_builder::QueryBuilder;
+pub use quote::{quote_column, quote_ident};
 use std::ops::{Bound, DerefMut, RangeBounds};
 
 #[derive(Debug, Clone)]
@@ -149,6 +151,26 @@ impl Sql {
     }
 }
 
+impl Sql {
+    /// Escape `\`, `%` and `_` so the value matches literally inside a `LIKE` pattern.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// assert_eq!(arel::Sql::escape_like(r#"50%_off\"#), r#"50\%\_off\\"#);
+    /// ```
+    pub fn escape_like<T: AsRef<str>>(value: T) -> String {
+        let mut escaped = String::new();
+        for char in value.as_ref().chars() {
+            if matches!(char, '\\' | '%' | '_') {
+                escaped.push('\\');
+            }
+            escaped.push(char);
+        }
+        escaped
+    }
+}
+
 impl Sql {
     #[allow(dead_code)]
     pub async fn exec<'a, E>(&self, executor: E) -> crate::Result<crate::db::DatabaseQueryResult>
@@ -221,6 +243,129 @@ impl Sql {
             Err(err) => Err(anyhow::anyhow!(err.to_string()).into()),
         }
     }
+    /// Run an `INSERT` or `UPDATE` of `M` and read the written row back from `RETURNING *`.
+    ///
+    /// `written` and `primary_values` are only needed on mysql, see the mysql build of this method.
+    #[cfg(not(feature = "mysql"))]
+    pub async fn fetch_written_as_with_exec<'a, M, E>(&self, _written: Vec<(&str, crate::Value)>, _primary_values: Vec<crate::Value>, executor: E) -> crate::Result<M>
+    where
+        M: crate::Arel,
+        for<'b> M: Send + Unpin + sqlx::FromRow<'b, crate::db::DatabaseRow>,
+        E: crate::db::WriteExecutor<'a>,
+    {
+        self.fetch_one_as_with_exec(executor).await
+    }
+    /// [`Sql::fetch_written_as_with_exec`] of an upsert, `None` when `DO NOTHING` skipped the row.
+    ///
+    /// `unique_keys` are only needed on mysql, see the mysql build of this method.
+    #[cfg(not(feature = "mysql"))]
+    pub async fn fetch_written_optional_as_with_exec<'a, M, E>(&self, _written: Vec<(&str, crate::Value)>, _primary_values: Vec<crate::Value>, _unique_keys: &[String], executor: E) -> crate::Result<Option<M>>
+    where
+        M: crate::Arel,
+        for<'b> M: Send + Unpin + sqlx::FromRow<'b, crate::db::DatabaseRow>,
+        E: crate::db::WriteExecutor<'a>,
+    {
+        self.fetch_one_optional_as_with_exec(executor).await
+    }
+    /// Run an `INSERT` or `UPDATE` of `M` and read the written row back, mysql has no `RETURNING` so it is
+    /// selected by primary key on the same connection.
+    ///
+    /// `written` are the columns the statement set and `primary_values` the primary key before it ran (empty
+    /// for an insert), a single primary key found in neither is the `LAST_INSERT_ID()` of the statement.
+    ///
+    /// A primary key the database generates other than by `AUTO_INCREMENT`, e.g. a `DEFAULT (UUID())` column,
+    /// can not be found and is an error: set such keys before inserting.
+    #[cfg(feature = "mysql")]
+    pub async fn fetch_written_as_with_exec<'a, M, E>(&self, written: Vec<(&str, crate::Value)>, primary_values: Vec<crate::Value>, executor: E) -> crate::Result<M>
+    where
+        M: crate::Arel,
+        for<'b> M: Send + Unpin + sqlx::FromRow<'b, crate::db::DatabaseRow>,
+        E: crate::db::WriteExecutor<'a>,
+    {
+        match self.fetch_written_optional_as_with_exec(written, primary_values, &[], executor).await? {
+            Some(model) => Ok(model),
+            None => Err(anyhow::anyhow!(sqlx::Error::RowNotFound.to_string()).into()),
+        }
+    }
+    /// [`Sql::fetch_written_as_with_exec`] of an upsert, `None` when no row was written.
+    ///
+    /// A row updated on conflict keeps its primary key out of `LAST_INSERT_ID()`, it is selected by the written
+    /// values of `unique_keys` (the conflict columns) when the primary key was not written.
+    #[cfg(feature = "mysql")]
+    pub async fn fetch_written_optional_as_with_exec<'a, M, E>(&self, written: Vec<(&str, crate::Value)>, primary_values: Vec<crate::Value>, unique_keys: &[String], executor: E) -> crate::Result<Option<M>>
+    where
+        M: crate::Arel,
+        for<'b> M: Send + Unpin + sqlx::FromRow<'b, crate::db::DatabaseRow>,
+        E: crate::db::WriteExecutor<'a>,
+    {
+        let mut conn = executor.acquire().await?;
+        let result = self.exec(&mut *conn).await?;
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+        let table_name = M::table_name();
+        let primary_keys = M::primary_keys();
+        let written_value = |key: &str| written.iter().find(|(field, _)| *field == key).map(|(_, value)| value.clone());
+        let primary_known = primary_values.len() == primary_keys.len() || primary_keys.iter().all(|primary_key| written_value(primary_key).is_some());
+        let unique_lookup: Option<Vec<(&str, crate::Value)>> = unique_keys.iter().map(|key| written_value(key).map(|value| (key.as_str(), value))).collect();
+        let lookup = match unique_lookup {
+            Some(unique_lookup) if !primary_known && !unique_lookup.is_empty() => unique_lookup,
+            _ => {
+                let mut lookup = vec![];
+                for (idx, primary_key) in primary_keys.iter().enumerate() {
+                    let value = match written_value(primary_key) {
+                        Some(value) => value,
+                        None if primary_values.len() == primary_keys.len() => primary_values[idx].clone(),
+                        None if primary_keys.len() == 1 && result.last_insert_id() > 0 => (result.last_insert_id() as i64).into(),
+                        None => {
+                            return Err(crate::Error::Message(format!(
+                                "can not read back the row written to {}: primary key \"{}\" is neither set nor AUTO_INCREMENT, set it before writing",
+                                table_name, primary_key
+                            )))
+                        }
+                    };
+                    lookup.push((*primary_key, value));
+                }
+                lookup
+            }
+        };
+        let where_sqls = lookup
+            .into_iter()
+            .map(|(key, value)| {
+                let mut where_sql = Sql::new(format!("{} = ", quote_column(&table_name, key)));
+                where_sql.push_bind(value);
+                where_sql
+            })
+            .collect();
+        let mut select_sql = Sql::new(format!("SELECT * FROM {} WHERE ", quote_ident(&table_name)));
+        select_sql.push_sqls(where_sqls, " AND ");
+        select_sql.fetch_one_optional_as_with_exec(&mut *conn).await
+    }
+    /// Rows are decoded one at a time while the stream is polled, nothing is buffered besides the row in flight.
+    pub(crate) fn fetch_stream_as_with_exec<'a, T, E>(&self, executor: E) -> futures_core::stream::BoxStream<'a, crate::Result<T>>
+    where
+        for<'b> T: Send + Unpin + sqlx::FromRow<'b, crate::db::DatabaseRow> + 'a,
+        E: sqlx::Executor<'a, Database = crate::db::Database> + 'a,
+    {
+        use futures_util::{SinkExt, StreamExt};
+        let mut query_builder: QueryBuilder<'a> = match self.try_into() {
+            Ok(query_builder) => query_builder,
+            Err(err) => return futures_util::stream::once(futures_util::future::ready(Err(err))).boxed(),
+        };
+        // the sqlx stream borrows the query builder, so it is driven inside a future owning both and its rows are handed over a channel
+        let (mut sender, receiver) = futures_channel::mpsc::channel::<crate::Result<T>>(0);
+        let producer = async move {
+            let mut rows = query_builder.build_query_as::<T>().fetch(executor);
+            while let Some(row) = rows.next().await {
+                let row = row.map_err(|err| anyhow::anyhow!(err.to_string()).into());
+                if sender.send(row).await.is_err() {
+                    break;
+                }
+            }
+        };
+        let producer = futures_util::stream::once(producer).filter_map(|_| futures_util::future::ready(None));
+        futures_util::stream::select(receiver, producer).boxed()
+    }
 }
 
 #[cfg(test)]
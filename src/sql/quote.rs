@@ -0,0 +1,55 @@
+/// Quote an identifier for the backend: `"name"`, `` `name` `` on mysql.
+///
+/// Quote characters inside a name are doubled, dots split schema qualified names and `*` is left bare.
+///
+/// # Examples
+///
+/// ```
+/// use arel::sql::quote_ident;
+/// #[cfg(any(feature = "sqlite", feature = "postgres"))]
+/// {
+///     assert_eq!(quote_ident("user"), r#""user""#);
+///     assert_eq!(quote_ident("analytics.events"), r#""analytics"."events""#);
+///     assert_eq!(quote_ident(r#"odd"name"#), r#""odd""name""#);
+/// }
+/// #[cfg(feature = "mysql")]
+/// {
+///     assert_eq!(quote_ident("analytics.events"), "`analytics`.`events`");
+///     assert_eq!(quote_ident("odd`name"), "`odd``name`");
+/// }
+/// ```
+pub fn quote_ident<T: AsRef<str>>(name: T) -> String {
+    name.as_ref().split('.').map(quote_part).collect::<Vec<String>>().join(".")
+}
+
+/// `"table"."column"`, see [`quote_ident`].
+///
+/// # Examples
+///
+/// ```
+/// use arel::sql::quote_column;
+/// #[cfg(any(feature = "sqlite", feature = "postgres"))]
+/// assert_eq!(quote_column("analytics.events", "id"), r#""analytics"."events"."id""#);
+/// #[cfg(feature = "mysql")]
+/// assert_eq!(quote_column("user", "*"), "`user`.*");
+/// ```
+pub fn quote_column<T: AsRef<str>, C: AsRef<str>>(table: T, column: C) -> String {
+    format!("{}.{}", quote_ident(table), quote_part(column.as_ref()))
+}
+
+fn quote_part(part: &str) -> String {
+    if part == "*" {
+        return part.to_string();
+    }
+    let quote = crate::db::dialect::compiled().quote_char();
+    let mut quoted = String::with_capacity(part.len() + 2);
+    quoted.push(quote);
+    for c in part.chars() {
+        if c == quote {
+            quoted.push(quote);
+        }
+        quoted.push(c);
+    }
+    quoted.push(quote);
+    quoted
+}
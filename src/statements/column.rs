@@ -0,0 +1,119 @@
+use crate::Arel;
+use std::marker::PhantomData;
+
+/// A typed column handle generated by `#[arel]`, carrying the real column name and the Rust value type of the field.
+///
+/// # Examples
+///
+/// ```
+/// use arel::prelude::*;
+/// #[arel]
+/// struct User {
+///     id: i32,
+///     #[arel(rename = "type")]
+///     r#type: String,
+/// }
+/// impl Arel for User {}
+/// assert_eq!(User::columns().id.name(), "id");
+/// assert_eq!(User::columns().r#type.name(), "type");
+/// assert_eq!(User::col().id.name(), "id");
+///
+/// // handles are public even for private fields
+/// mod models {
+///     use arel::prelude::*;
+///     #[arel]
+///     pub struct Wallet {
+///         amount: i32,
+///     }
+///     impl Arel for Wallet {}
+/// }
+/// assert_eq!(models::Wallet::columns().amount.name(), "amount");
+/// ```
+pub struct Column<M, V> {
+    name: &'static str,
+    _marker: PhantomData<fn() -> (M, V)>,
+}
+
+impl<M, V> Column<M, V> {
+    pub const fn new(name: &'static str) -> Self {
+        Self { name, _marker: PhantomData }
+    }
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl<M, V> Clone for Column<M, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<M, V> Copy for Column<M, V> {}
+
+impl<M, V> std::fmt::Debug for Column<M, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Column").field(&self.name).finish()
+    }
+}
+
+/// Anything naming a column of `M`: a plain `&str`/`String` key or a typed [`Column`] of the same model.
+pub trait ColumnName<M: Arel> {
+    fn column_name(&self) -> &str;
+}
+
+impl<M: Arel, K: AsRef<str>> ColumnName<M> for K {
+    fn column_name(&self) -> &str {
+        self.as_ref()
+    }
+}
+
+impl<M: Arel, V> ColumnName<M> for Column<M, V> {
+    fn column_name(&self) -> &str {
+        self.name
+    }
+}
+
+/// Marker types used to pick a [`ColumnFilter`] impl, never constructed.
+pub mod marker {
+    /// plain string key, any value that converts into `arel::Value`
+    pub struct Raw;
+    /// typed column compared with a single value
+    pub struct One;
+    /// typed column compared with a list of values (`IN`)
+    pub struct Many;
+}
+
+/// A column key together with the values that may be bound to it.
+///
+/// String keys accept every `Into<arel::Value>`, typed columns only accept values converting into the field type.
+///
+/// ```compile_fail
+/// use arel::prelude::*;
+/// #[arel]
+/// struct User {
+///     age: Option<i32>,
+/// }
+/// impl Arel for User {}
+/// User::query().r#where(User::columns().age, "x");
+/// ```
+pub trait ColumnFilter<M: Arel, V, X>: ColumnName<M> {
+    fn filter_value(value: V) -> crate::Value;
+}
+
+impl<M: Arel, K: AsRef<str>, V: Into<crate::Value>> ColumnFilter<M, V, marker::Raw> for K {
+    fn filter_value(value: V) -> crate::Value {
+        value.into()
+    }
+}
+
+impl<M: Arel, T: Into<crate::Value>, V: Into<T>> ColumnFilter<M, V, marker::One> for Column<M, T> {
+    fn filter_value(value: V) -> crate::Value {
+        Into::<T>::into(value).into()
+    }
+}
+
+impl<M: Arel, T: Into<crate::Value>, V: Into<T>> ColumnFilter<M, Vec<V>, marker::Many> for Column<M, T> {
+    fn filter_value(values: Vec<V>) -> crate::Value {
+        values.into_iter().map(|value| Into::<T>::into(value)).collect::<Vec<T>>().into()
+    }
+}
@@ -1,4 +1,5 @@
 use crate::prelude::Arel;
+use crate::sql::quote_ident;
 use crate::statements::ArelStatement;
 use std::{fmt::Debug, marker::PhantomData};
 
@@ -12,12 +13,12 @@ pub struct Delete<M: Arel> {
 impl<M: Arel> ArelStatement for Delete<M> {
     fn to_sql(&self) -> crate::Result<Option<crate::Sql>> {
         let table_name = M::table_name();
-        let mut final_sql = crate::Sql::new(format!(r#"DELETE FROM "{}" WHERE "#, table_name));
+        let mut final_sql = crate::Sql::new(format!("DELETE FROM {} WHERE ", quote_ident(&table_name)));
 
         let len = self.where_fields.len();
         for (idx, field) in self.where_fields.iter().enumerate() {
             let value = &self.where_values[idx];
-            final_sql.push_str_with_bind(format!(r#""{}" = "#, field), value.clone());
+            final_sql.push_str_with_bind(format!("{} = ", quote_ident(field)), value.clone());
             if idx < len - 1 {
                 final_sql.push_str(" AND ");
             }
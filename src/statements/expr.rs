@@ -0,0 +1,360 @@
+use crate::{
+    sql::{quote_column, quote_ident},
+    statements::column::{Column, ColumnName},
+    Arel,
+};
+
+/// A projection expression: columns, bound values, functions, window functions, `CASE WHEN` and arithmetic.
+///
+/// Strings convert into a quoted column name, like the column arguments of the query builder, typed columns into
+/// `"table"."column"` and other plain values into a bound parameter. Bind a string with [`Expr::value`].
+///
+/// # Examples
+///
+/// ```
+/// use arel::prelude::*;
+/// use arel::statements::expr::{Expr, Window};
+/// #[arel]
+/// struct User {
+///     age: i32,
+///     score: i32,
+///     #[arel(rename = "type")]
+///     r#type: String,
+/// }
+/// impl Arel for User {}
+/// let age = User::columns().age;
+/// let expr = (Expr::from(age) + 1).alias("next_age");
+/// #[cfg(feature = "sqlite")]
+/// assert_eq!(expr.into_sql().to_sql_string().unwrap(), r#"("user"."age" + ?) AS "next_age""#);
+///
+/// let expr = Expr::row_number().over(Window::new().partition_by(User::columns().r#type).order_by(User::columns().score, arel::SortConst::Desc));
+/// #[cfg(not(feature = "mysql"))]
+/// assert_eq!(expr.into_sql().to_sql_string().unwrap(), r#"ROW_NUMBER() OVER (PARTITION BY "user"."type" ORDER BY "user"."score" DESC)"#);
+///
+/// let expr = Expr::case().when(Expr::from(age).gte(18), Expr::value("adult")).otherwise(Expr::value("minor")).alias("stage");
+/// #[cfg(feature = "sqlite")]
+/// assert_eq!(expr.into_sql().to_sql_string().unwrap(), r#"CASE WHEN "user"."age" >= ? THEN ? ELSE ? END AS "stage""#);
+///
+/// // strings are columns, not bound values
+/// let expr = Expr::sum("age").over(Window::new().partition_by("type").order_by("score", arel::SortConst::Asc));
+/// #[cfg(not(feature = "mysql"))]
+/// assert_eq!(expr.into_sql().to_sql_string().unwrap(), r#"SUM("age") OVER (PARTITION BY "type" ORDER BY "score" ASC)"#);
+/// #[cfg(feature = "mysql")]
+/// assert_eq!(expr.into_sql().to_sql_string().unwrap(), "SUM(`age`) OVER (PARTITION BY `type` ORDER BY `score` ASC)");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Expr {
+    sql: crate::Sql,
+}
+
+impl From<&str> for Expr {
+    fn from(column: &str) -> Self {
+        Self::raw(quote_ident(column))
+    }
+}
+
+impl From<String> for Expr {
+    fn from(column: String) -> Self {
+        Self::raw(quote_ident(column))
+    }
+}
+
+impl From<&String> for Expr {
+    fn from(column: &String) -> Self {
+        Self::raw(quote_ident(column))
+    }
+}
+
+macro_rules! impl_from_value {
+    ($($type:ty),*) => {
+        $(
+            impl From<$type> for Expr {
+                fn from(value: $type) -> Self {
+                    Self::value(value)
+                }
+            }
+        )*
+    };
+}
+impl_from_value!(
+    crate::Value,
+    bool,
+    i8,
+    i16,
+    i32,
+    i64,
+    f32,
+    f64,
+    bytes::Bytes,
+    serde_json::Value,
+    chrono::DateTime<chrono::FixedOffset>,
+    chrono::DateTime<chrono::Utc>,
+    chrono::NaiveDateTime,
+    chrono::NaiveDate,
+    chrono::NaiveTime
+);
+
+#[cfg(any(feature = "sqlite", feature = "mysql"))]
+impl_from_value!(u8, u16, u32);
+#[cfg(feature = "mysql")]
+impl_from_value!(u64);
+
+impl<T: Clone + Into<crate::Value> + Default> From<Option<T>> for Expr {
+    fn from(value: Option<T>) -> Self {
+        Self::value(value)
+    }
+}
+
+impl<M: Arel, V> From<Column<M, V>> for Expr {
+    fn from(column: Column<M, V>) -> Self {
+        Self::column::<M, _>(column)
+    }
+}
+
+/// The value side of an assignment (`set`, `update_all`, upsert updates): plain values, strings included, are
+/// bound, an [`Expr`] or typed column is written as is.
+pub trait AssignValue {
+    fn into_expr(self) -> Expr;
+}
+
+impl<V: Into<crate::Value>> AssignValue for V {
+    fn into_expr(self) -> Expr {
+        Expr::value(self)
+    }
+}
+
+impl AssignValue for Expr {
+    fn into_expr(self) -> Expr {
+        self
+    }
+}
+
+impl<M: Arel, V> AssignValue for Column<M, V> {
+    fn into_expr(self) -> Expr {
+        Expr::from(self)
+    }
+}
+
+impl Expr {
+    /// `"table"."column"`
+    pub fn column<M: Arel, T: ColumnName<M>>(column: T) -> Self {
+        Self::raw(quote_column(M::table_name(), column.column_name()))
+    }
+    /// a bound parameter
+    pub fn value<V: Into<crate::Value>>(value: V) -> Self {
+        let mut sql = crate::Sql::default();
+        sql.push_bind(value);
+        Self { sql }
+    }
+    /// The value `column` had in the row an upsert failed to insert: `excluded."column"`, `VALUES("column")` on mysql.
+    pub fn excluded<T: AsRef<str>>(column: T) -> Self {
+        if crate::db::dialect::compiled().upsert_style() == crate::db::dialect::UpsertStyle::OnDuplicateKey {
+            Self::raw(format!("VALUES({})", quote_ident(column)))
+        } else {
+            Self::raw(format!("excluded.{}", quote_ident(column)))
+        }
+    }
+    /// raw sql, written as is
+    pub fn raw<S: Into<crate::Sql>>(sql: S) -> Self {
+        Self { sql: sql.into() }
+    }
+    /// `name(arg, ..)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::statements::expr::Expr;
+    /// let expr = Expr::func("ROUND", vec![Expr::raw("price"), Expr::raw("2")]);
+    /// assert_eq!(expr.into_sql().to_sql_string().unwrap(), "ROUND(price, 2)");
+    /// ```
+    pub fn func<N: AsRef<str>, E: Into<Expr>>(name: N, args: Vec<E>) -> Self {
+        let mut sql = crate::Sql::new(format!("{}(", name.as_ref()));
+        sql.push_sqls(args.into_iter().map(|arg| arg.into().sql).collect(), ", ").push_str(")");
+        Self { sql }
+    }
+    /// `COUNT(*)`
+    pub fn count_all() -> Self {
+        Self::raw("COUNT(*)")
+    }
+    pub fn count<E: Into<Expr>>(expr: E) -> Self {
+        Self::func("COUNT", vec![expr])
+    }
+    /// `COUNT(DISTINCT expr)`
+    pub fn count_distinct<E: Into<Expr>>(expr: E) -> Self {
+        let mut sql = crate::Sql::new("COUNT(DISTINCT ");
+        sql.push_sql(expr.into().sql).push_str(")");
+        Self { sql }
+    }
+    pub fn sum<E: Into<Expr>>(expr: E) -> Self {
+        Self::func("SUM", vec![expr])
+    }
+    pub fn avg<E: Into<Expr>>(expr: E) -> Self {
+        Self::func("AVG", vec![expr])
+    }
+    pub fn min<E: Into<Expr>>(expr: E) -> Self {
+        Self::func("MIN", vec![expr])
+    }
+    pub fn max<E: Into<Expr>>(expr: E) -> Self {
+        Self::func("MAX", vec![expr])
+    }
+    pub fn coalesce<E: Into<Expr>>(exprs: Vec<E>) -> Self {
+        Self::func("COALESCE", exprs)
+    }
+    pub fn row_number() -> Self {
+        Self::raw("ROW_NUMBER()")
+    }
+    pub fn rank() -> Self {
+        Self::raw("RANK()")
+    }
+    pub fn dense_rank() -> Self {
+        Self::raw("DENSE_RANK()")
+    }
+    /// `LAG(expr, offset)`
+    pub fn lag<E: Into<Expr>>(expr: E, offset: usize) -> Self {
+        Self::func("LAG", vec![expr.into(), Self::raw(offset)])
+    }
+    /// `LEAD(expr, offset)`
+    pub fn lead<E: Into<Expr>>(expr: E, offset: usize) -> Self {
+        Self::func("LEAD", vec![expr.into(), Self::raw(offset)])
+    }
+    pub fn case() -> Case {
+        Case::default()
+    }
+    /// `expr OVER (window)`
+    pub fn over(self, window: Window) -> Self {
+        let mut sql = self.sql;
+        sql.push_str(" OVER (").push_sql(window.to_sql()).push_str(")");
+        Self { sql }
+    }
+    /// `expr AS "alias"`
+    pub fn alias<A: AsRef<str>>(self, alias: A) -> Self {
+        let mut sql = self.sql;
+        sql.push_str(format!(" AS {}", quote_ident(alias)));
+        Self { sql }
+    }
+    pub fn eq<E: Into<Expr>>(self, rhs: E) -> Self {
+        self.binary(" = ", rhs)
+    }
+    pub fn ne<E: Into<Expr>>(self, rhs: E) -> Self {
+        self.binary(" != ", rhs)
+    }
+    pub fn gt<E: Into<Expr>>(self, rhs: E) -> Self {
+        self.binary(" > ", rhs)
+    }
+    pub fn gte<E: Into<Expr>>(self, rhs: E) -> Self {
+        self.binary(" >= ", rhs)
+    }
+    pub fn lt<E: Into<Expr>>(self, rhs: E) -> Self {
+        self.binary(" < ", rhs)
+    }
+    pub fn lte<E: Into<Expr>>(self, rhs: E) -> Self {
+        self.binary(" <= ", rhs)
+    }
+    pub fn and<E: Into<Expr>>(self, rhs: E) -> Self {
+        self.binary(" AND ", rhs).paren()
+    }
+    pub fn or<E: Into<Expr>>(self, rhs: E) -> Self {
+        self.binary(" OR ", rhs).paren()
+    }
+    pub fn is_null(self) -> Self {
+        let mut sql = self.sql;
+        sql.push_str(" IS NULL");
+        Self { sql }
+    }
+    pub fn is_not_null(self) -> Self {
+        let mut sql = self.sql;
+        sql.push_str(" IS NOT NULL");
+        Self { sql }
+    }
+    pub fn into_sql(self) -> crate::Sql {
+        self.sql
+    }
+    fn binary<E: Into<Expr>>(self, operator: &str, rhs: E) -> Self {
+        let mut sql = self.sql;
+        sql.push_str(operator).push_sql(rhs.into().sql);
+        Self { sql }
+    }
+    fn paren(self) -> Self {
+        let mut sql = crate::Sql::new("(");
+        sql.push_sql(self.sql).push_str(")");
+        Self { sql }
+    }
+}
+
+macro_rules! impl_arithmetic {
+    ($trait:ident, $method:ident, $operator:literal) => {
+        impl<E: Into<Expr>> std::ops::$trait<E> for Expr {
+            type Output = Expr;
+            fn $method(self, rhs: E) -> Self::Output {
+                self.binary($operator, rhs).paren()
+            }
+        }
+    };
+}
+impl_arithmetic!(Add, add, " + ");
+impl_arithmetic!(Sub, sub, " - ");
+impl_arithmetic!(Mul, mul, " * ");
+impl_arithmetic!(Div, div, " / ");
+impl_arithmetic!(Rem, rem, " % ");
+
+/// `CASE WHEN .. THEN .. [ELSE ..] END`, finished by [`Case::otherwise`] or [`Case::end`].
+#[derive(Debug, Clone, Default)]
+pub struct Case {
+    sqls: Vec<crate::Sql>,
+}
+
+impl Case {
+    pub fn when<C: Into<Expr>, T: Into<Expr>>(mut self, condition: C, then: T) -> Self {
+        let mut sql = crate::Sql::new("WHEN ");
+        sql.push_sql(condition.into().sql).push_str(" THEN ").push_sql(then.into().sql);
+        self.sqls.push(sql);
+        self
+    }
+    pub fn otherwise<E: Into<Expr>>(mut self, expr: E) -> Expr {
+        let mut sql = crate::Sql::new("ELSE ");
+        sql.push_sql(expr.into().sql);
+        self.sqls.push(sql);
+        self.end()
+    }
+    pub fn end(self) -> Expr {
+        let mut sql = crate::Sql::new("CASE ");
+        sql.push_sqls(self.sqls, " ").push_str(" END");
+        Expr { sql }
+    }
+}
+
+/// The `OVER (PARTITION BY .. ORDER BY ..)` part of a window function.
+#[derive(Debug, Clone, Default)]
+pub struct Window {
+    partitions: Vec<crate::Sql>,
+    orders: Vec<crate::Sql>,
+}
+
+impl Window {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn partition_by<E: Into<Expr>>(mut self, expr: E) -> Self {
+        self.partitions.push(expr.into().sql);
+        self
+    }
+    pub fn order_by<E: Into<Expr>>(mut self, expr: E, sort_type: crate::SortConst) -> Self {
+        let mut sql = expr.into().sql;
+        sql.push_str(format!(" {}", sort_type));
+        self.orders.push(sql);
+        self
+    }
+    fn to_sql(&self) -> crate::Sql {
+        let mut sql = crate::Sql::default();
+        if !self.partitions.is_empty() {
+            sql.push_str("PARTITION BY ").push_sqls(self.partitions.clone(), ", ");
+        }
+        if !self.orders.is_empty() {
+            if !self.partitions.is_empty() {
+                sql.push_str(" ");
+            }
+            sql.push_str("ORDER BY ").push_sqls(self.orders.clone(), ", ");
+        }
+        sql
+    }
+}
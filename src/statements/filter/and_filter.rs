@@ -1,18 +1,38 @@
 use super::ArelSubFilterStatement;
 
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct AndFilter {
-    pub(crate) sqls: Vec<crate::Sql>,
+    // each predicate with the column it filters, `None` for raw sql
+    pub(crate) sqls: Vec<(Option<String>, crate::Sql)>,
 }
 
-impl ArelSubFilterStatement for AndFilter {
-    fn sqls(&self) -> &Vec<crate::Sql> {
-        &self.sqls
-    }
-    fn sqls_mut(&mut self) -> &mut Vec<crate::Sql> {
-        &mut self.sqls
+impl AndFilter {
+    pub(crate) fn push<S: Into<crate::Sql>>(&mut self, column: Option<&str>, sql: S) -> &mut Self {
+        self.sqls.push((column.map(|column| column.to_string()), sql.into()));
+        self
     }
+}
+
+impl ArelSubFilterStatement for AndFilter {
     fn join_str(&self) -> &'static str {
         " AND "
     }
+    fn to_sql(&self) -> crate::Result<Option<crate::Sql>> {
+        Ok(super::sqls_to_sql(&self.sqls.iter().map(|(_, sql)| sql.clone()).collect::<Vec<_>>(), self.join_str()))
+    }
+    fn is_compound(&self) -> bool {
+        self.sqls.len() > 1
+    }
+    fn unfilter_starts_with(&mut self, start: &str) {
+        self.sqls.retain(|(_, sql)| !sql.raw_value.starts_with(start));
+    }
+    fn unfilter_column(&mut self, column: &str, start: &str) {
+        self.sqls.retain(|(filtered, sql)| match filtered {
+            Some(filtered) => filtered != column,
+            None => !sql.raw_value.starts_with(start),
+        });
+    }
+    fn clone_box(&self) -> super::BoxSubFilter {
+        Box::new(self.clone())
+    }
 }
@@ -0,0 +1,29 @@
+use super::ArelSubFilterStatement;
+
+#[derive(Debug, Clone)]
+pub struct ErrorFilter {
+    message: String,
+}
+
+impl ErrorFilter {
+    pub(super) fn new(message: String) -> Self {
+        Self { message }
+    }
+}
+
+impl ArelSubFilterStatement for ErrorFilter {
+    fn join_str(&self) -> &'static str {
+        " AND "
+    }
+    fn to_sql(&self) -> crate::Result<Option<crate::Sql>> {
+        Err(crate::Error::Message(self.message.clone()))
+    }
+    fn is_compound(&self) -> bool {
+        false
+    }
+    fn unfilter_starts_with(&mut self, _start: &str) {}
+    fn unfilter_column(&mut self, _column: &str, _start: &str) {}
+    fn clone_box(&self) -> super::BoxSubFilter {
+        Box::new(self.clone())
+    }
+}
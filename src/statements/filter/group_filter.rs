@@ -0,0 +1,45 @@
+use super::{ArelSubFilterStatement, BoxSubFilter};
+
+#[derive(Debug, Clone)]
+pub struct GroupFilter {
+    join_str: &'static str,
+    not: bool,
+    sub_filters: Vec<BoxSubFilter>,
+}
+
+impl GroupFilter {
+    pub(super) fn new(join_str: &'static str, not: bool, sub_filters: Vec<BoxSubFilter>) -> Self {
+        Self { join_str, not, sub_filters }
+    }
+}
+
+impl ArelSubFilterStatement for GroupFilter {
+    fn join_str(&self) -> &'static str {
+        self.join_str
+    }
+    fn to_sql(&self) -> crate::Result<Option<crate::Sql>> {
+        if let Some(sql) = super::sub_filters_to_sql(&self.sub_filters)? {
+            let mut final_sql = crate::Sql::new(if self.not { "NOT (" } else { "(" });
+            final_sql.push_sql(sql).push_str(")");
+            Ok(Some(final_sql))
+        } else {
+            Ok(None)
+        }
+    }
+    fn is_compound(&self) -> bool {
+        false
+    }
+    fn unfilter_starts_with(&mut self, start: &str) {
+        for sub_filter in self.sub_filters.iter_mut() {
+            sub_filter.unfilter_starts_with(start);
+        }
+    }
+    fn unfilter_column(&mut self, column: &str, start: &str) {
+        for sub_filter in self.sub_filters.iter_mut() {
+            sub_filter.unfilter_column(column, start);
+        }
+    }
+    fn clone_box(&self) -> super::BoxSubFilter {
+        Box::new(self.clone())
+    }
+}
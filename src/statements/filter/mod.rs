@@ -1,36 +1,90 @@
 mod and_filter;
+mod error_filter;
+mod group_filter;
+mod operator;
 mod or_filter;
 
 pub(crate) use and_filter::AndFilter;
+use error_filter::ErrorFilter;
+use group_filter::GroupFilter;
+pub use operator::Operator;
 pub(crate) use or_filter::OrFilter;
 
-use crate::{statements::ArelStatement, Arel};
+use crate::{
+    sql::quote_column,
+    statements::{
+        column::{ColumnFilter, ColumnName},
+        ArelStatement,
+    },
+    Arel,
+};
 use std::{fmt::Debug, marker::PhantomData, ops::Deref};
 
+type BoxSubFilter = Box<dyn ArelSubFilterStatement + Sync + Send + 'static>;
+
 trait ArelSubFilterStatement: Debug {
-    fn sqls(&self) -> &Vec<crate::Sql>;
-    fn sqls_mut(&mut self) -> &mut Vec<crate::Sql>;
     fn join_str(&self) -> &'static str;
-    fn to_sql(&self) -> crate::Result<Option<crate::Sql>> {
-        let mut final_sql = crate::Sql::default();
-        let len = self.sqls().len();
-        if len > 0 {
-            for (idx, sql) in self.sqls().iter().enumerate() {
-                final_sql.push_sql(sql.clone());
-                if idx < len - 1 {
-                    final_sql.push_str(self.join_str());
-                }
+    fn to_sql(&self) -> crate::Result<Option<crate::Sql>>;
+    /// more than one predicate joined by `join_str`, needs parentheses inside an `OR`
+    fn is_compound(&self) -> bool;
+    fn unfilter_starts_with(&mut self, start: &str);
+    /// drop the predicates on `column`, and raw sql starting with `start`
+    fn unfilter_column(&mut self, column: &str, start: &str);
+    fn clone_box(&self) -> BoxSubFilter;
+}
+
+impl Clone for BoxSubFilter {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+fn sqls_to_sql(sqls: &[crate::Sql], join_str: &str) -> Option<crate::Sql> {
+    if sqls.is_empty() {
+        return None;
+    }
+    let mut final_sql = crate::Sql::default();
+    final_sql.push_sqls(sqls.to_vec(), join_str);
+    Some(final_sql)
+}
+
+fn sub_filters_to_sql(sub_filters: &[BoxSubFilter]) -> crate::Result<Option<crate::Sql>> {
+    let mut final_sql = crate::Sql::new("");
+    let mut is_empty = true;
+    for sub_filter in sub_filters.iter() {
+        if let Some(sql) = sub_filter.to_sql()? {
+            if !is_empty {
+                final_sql.push_str(sub_filter.join_str());
+            }
+            if sub_filter.join_str() == " OR " && sub_filter.is_compound() {
+                final_sql.push_str("(").push_sql(sql).push_str(")");
+            } else {
+                final_sql.push_sql(sql);
             }
+            is_empty = false;
         }
+    }
+    if is_empty {
+        Ok(None)
+    } else {
         Ok(Some(final_sql))
     }
 }
 
 #[derive(Debug)]
 pub struct Filter<M: crate::Arel> {
-    sub_filters: Vec<Box<dyn ArelSubFilterStatement + Sync + Send + 'static>>,
+    sub_filters: Vec<BoxSubFilter>,
     _marker: PhantomData<M>,
 }
+
+impl<M: crate::Arel> Clone for Filter<M> {
+    fn clone(&self) -> Self {
+        Self {
+            sub_filters: self.sub_filters.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
 impl<M: crate::Arel> Default for Filter<M> {
     fn default() -> Self {
         Self {
@@ -42,26 +96,7 @@ impl<M: crate::Arel> Default for Filter<M> {
 
 impl<M: crate::Arel> ArelStatement for Filter<M> {
     fn to_sql(&self) -> crate::Result<Option<crate::Sql>> {
-        let sub_filters: Vec<&Box<dyn ArelSubFilterStatement + Sync + Send + 'static>> = self.sub_filters.iter().collect();
-        if self.sub_filters.len() > 0 {
-            let mut final_sql = crate::Sql::new("");
-            for (idx, sub_filter) in sub_filters.into_iter().enumerate() {
-                let sql = sub_filter.to_sql()?;
-                if let Some(sql) = sql {
-                    if idx >= 1 {
-                        final_sql.push_str(sub_filter.join_str());
-                    }
-                    if sub_filter.join_str() == " OR " && sub_filter.sqls().len() > 1 {
-                        final_sql.push_str("(").push_sql(sql).push_str(")");
-                    } else {
-                        final_sql.push_sql(sql);
-                    }
-                }
-            }
-            Ok(Some(final_sql))
-        } else {
-            Ok(None)
-        }
+        sub_filters_to_sql(&self.sub_filters)
     }
 }
 
@@ -76,16 +111,26 @@ impl<M: Arel> Filter<M> {
     /// impl Arel for User {}
     /// let mut filter = Filter::<User>::default();
     /// filter.and_filter("username", "sanmu");
-    /// #[cfg(any(feature = "sqlite", feature = "mysql"))]
+    /// #[cfg(feature = "sqlite")]
     /// assert_eq!(filter.to_sql().unwrap().unwrap().to_sql_string().unwrap(), r#""user"."username" = ?"#);
     ///
     /// filter.and_filter("age", vec![18, 20]);
-    /// #[cfg(any(feature = "sqlite", feature = "mysql"))]
+    /// #[cfg(feature = "sqlite")]
     /// assert_eq!(filter.to_sql().unwrap().unwrap().to_sql_string().unwrap(), r#""user"."username" = ? AND "user"."age" IN (?, ?)"#);
     ///
+    /// #[arel]
+    /// struct Admin {
+    ///     age: i32,
+    /// }
+    /// impl Arel for Admin {}
+    /// let mut filter = Filter::<Admin>::default();
+    /// filter.and_filter(Admin::columns().age, vec![18, 20]);
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(filter.to_sql().unwrap().unwrap().to_sql_string().unwrap(), r#""admin"."age" IN (?, ?)"#);
+    ///
     /// ```
-    pub fn and_filter<K: AsRef<str>, V: Into<crate::Value>>(&mut self, key: K, value: V) -> &mut Self {
-        self.and_filter_pairs(vec![(key, value)])
+    pub fn and_filter<K: ColumnFilter<M, V, X>, V, X>(&mut self, key: K, value: V) -> &mut Self {
+        self.and_filter_pairs(vec![(key.column_name(), K::filter_value(value))])
     }
     /// # Examples
     ///
@@ -97,11 +142,11 @@ impl<M: Arel> Filter<M> {
     /// impl Arel for User {}
     /// let mut filter = Filter::<User>::default();
     /// filter.and_filter_pairs(vec![("username", Into::<arel::Value>::into("sanmu")), ("age", Into::<arel::Value>::into(vec![18, 20]))]);
-    /// #[cfg(any(feature = "sqlite", feature = "mysql"))]
+    /// #[cfg(feature = "sqlite")]
     /// assert_eq!(filter.to_sql().unwrap().unwrap().to_sql_string().unwrap(), r#""user"."username" = ? AND "user"."age" IN (?, ?)"#);
     ///
     /// filter.and_filter("gender", "male");
-    /// #[cfg(any(feature = "sqlite", feature = "mysql"))]
+    /// #[cfg(feature = "sqlite")]
     /// assert_eq!(filter.to_sql().unwrap().unwrap().to_sql_string().unwrap(), r#""user"."username" = ? AND "user"."age" IN (?, ?) AND "user"."gender" = ?"#);
     ///
     /// ```
@@ -111,7 +156,7 @@ impl<M: Arel> Filter<M> {
         for (key, value) in pairs.into_iter() {
             let mut sql = crate::Sql::default();
             let value: crate::Value = value.into();
-            sql.push_str(format!(r#""{}"."{}""#, table_name, key.as_ref()));
+            sql.push_str(quote_column(&table_name, &key));
             match &value {
                 crate::Value::Array(arrary) => match arrary.deref() {
                     Some(arr) => {
@@ -129,14 +174,21 @@ impl<M: Arel> Filter<M> {
                     }
                 }
             }
-            and_filter.sqls.push(sql);
+            and_filter.push(Some(key.as_ref()), sql);
         }
         self.sub_filters.push(Box::new(and_filter));
         self
     }
     pub fn and_filter_sql<S: Into<crate::Sql>>(&mut self, sql: S) -> &mut Self {
         let mut and_filter = AndFilter::default();
-        and_filter.sqls.push(sql.into());
+        and_filter.push(None, sql);
+        self.sub_filters.push(Box::new(and_filter));
+        self
+    }
+    /// raw sql filtering `column`, removed by [`Filter::unfilter`] of that column
+    pub(crate) fn and_filter_column_sql<S: Into<crate::Sql>>(&mut self, column: &str, sql: S) -> &mut Self {
+        let mut and_filter = AndFilter::default();
+        and_filter.push(Some(column), sql);
         self.sub_filters.push(Box::new(and_filter));
         self
     }
@@ -150,16 +202,16 @@ impl<M: Arel> Filter<M> {
     /// impl Arel for User {}
     /// let mut filter = Filter::<User>::default();
     /// filter.and_not_filter("username", "sanmu");
-    /// #[cfg(any(feature = "sqlite", feature = "mysql"))]
+    /// #[cfg(feature = "sqlite")]
     /// assert_eq!(filter.to_sql().unwrap().unwrap().to_sql_string().unwrap(), r#""user"."username" != ?"#);
     ///
     /// filter.and_not_filter("aga", vec![18, 20]);
-    /// #[cfg(any(feature = "sqlite", feature = "mysql"))]
+    /// #[cfg(feature = "sqlite")]
     /// assert_eq!(filter.to_sql().unwrap().unwrap().to_sql_string().unwrap(), r#""user"."username" != ? AND "user"."aga" NOT IN (?, ?)"#);
     ///
     /// ```
-    pub fn and_not_filter<K: AsRef<str>, V: Into<crate::Value>>(&mut self, key: K, value: V) -> &mut Self {
-        self.and_not_filter_pairs(vec![(key, value)])
+    pub fn and_not_filter<K: ColumnFilter<M, V, X>, V, X>(&mut self, key: K, value: V) -> &mut Self {
+        self.and_not_filter_pairs(vec![(key.column_name(), K::filter_value(value))])
     }
     pub fn and_not_filter_pairs<K: AsRef<str>, V: Into<crate::Value>>(&mut self, pairs: Vec<(K, V)>) -> &mut Self {
         let table_name = M::table_name();
@@ -167,7 +219,7 @@ impl<M: Arel> Filter<M> {
         for (key, value) in pairs.into_iter() {
             let mut sql = crate::Sql::default();
             let value: crate::Value = value.into();
-            sql.push_str(format!(r#""{}"."{}""#, table_name, key.as_ref()));
+            sql.push_str(quote_column(&table_name, &key));
             match &value {
                 crate::Value::Array(arrary) => match arrary.deref() {
                     Some(arr) => {
@@ -185,7 +237,7 @@ impl<M: Arel> Filter<M> {
                     }
                 }
             }
-            and_filter.sqls.push(sql);
+            and_filter.push(Some(key.as_ref()), sql);
         }
         self.sub_filters.push(Box::new(and_filter));
         self
@@ -200,20 +252,20 @@ impl<M: Arel> Filter<M> {
     /// impl Arel for User {}
     /// let mut filter = Filter::<User>::default();
     /// filter.or_filter("username", "sanmu");
-    /// #[cfg(any(feature = "sqlite", feature = "mysql"))]
+    /// #[cfg(feature = "sqlite")]
     /// assert_eq!(filter.to_sql().unwrap().unwrap().to_sql_string().unwrap(), r#""user"."username" = ?"#);
     ///
     /// filter.or_filter("age", vec![18, 20]);
-    /// #[cfg(any(feature = "sqlite", feature = "mysql"))]
+    /// #[cfg(feature = "sqlite")]
     /// assert_eq!(filter.to_sql().unwrap().unwrap().to_sql_string().unwrap(), r#""user"."username" = ? OR "user"."age" IN (?, ?)"#);
     ///
     /// filter.and_filter("gender", "male");
-    /// #[cfg(any(feature = "sqlite", feature = "mysql"))]
+    /// #[cfg(feature = "sqlite")]
     /// assert_eq!(filter.to_sql().unwrap().unwrap().to_sql_string().unwrap(), r#""user"."username" = ? OR "user"."age" IN (?, ?) AND "user"."gender" = ?"#);
     ///
     /// ```
-    pub fn or_filter<K: AsRef<str>, V: Into<crate::Value>>(&mut self, key: K, value: V) -> &mut Self {
-        self.or_filter_pairs(vec![(key, value)])
+    pub fn or_filter<K: ColumnFilter<M, V, X>, V, X>(&mut self, key: K, value: V) -> &mut Self {
+        self.or_filter_pairs(vec![(key.column_name(), K::filter_value(value))])
     }
     /// # Examples
     ///
@@ -225,11 +277,11 @@ impl<M: Arel> Filter<M> {
     /// impl Arel for User {}
     /// let mut filter = Filter::<User>::default();
     /// filter.or_filter_pairs(vec![("username", Into::<arel::Value>::into("sanmu")), ("age", Into::<arel::Value>::into(vec![18, 20]))]);
-    /// #[cfg(any(feature = "sqlite", feature = "mysql"))]
+    /// #[cfg(feature = "sqlite")]
     /// assert_eq!(filter.to_sql().unwrap().unwrap().to_sql_string().unwrap(), r#"("user"."username" = ? OR "user"."age" IN (?, ?))"#);
     ///
     /// filter.or_filter("gender", "male");
-    /// #[cfg(any(feature = "sqlite", feature = "mysql"))]
+    /// #[cfg(feature = "sqlite")]
     /// assert_eq!(filter.to_sql().unwrap().unwrap().to_sql_string().unwrap(), r#"("user"."username" = ? OR "user"."age" IN (?, ?)) OR "user"."gender" = ?"#);
     ///
     /// ```
@@ -239,7 +291,7 @@ impl<M: Arel> Filter<M> {
         for (key, value) in pairs.into_iter() {
             let mut sql = crate::Sql::default();
             let value: crate::Value = value.into();
-            sql.push_str(format!(r#""{}"."{}""#, table_name, key.as_ref()));
+            sql.push_str(quote_column(&table_name, &key));
             match &value {
                 crate::Value::Array(arrary) => match arrary.deref() {
                     Some(arr) => {
@@ -257,14 +309,14 @@ impl<M: Arel> Filter<M> {
                     }
                 }
             }
-            or_filter.sqls.push(sql);
+            or_filter.push(Some(key.as_ref()), sql);
         }
         self.sub_filters.push(Box::new(or_filter));
         self
     }
     pub fn or_filter_sql<S: Into<crate::Sql>>(&mut self, sql: S) -> &mut Self {
         let mut or_filter = OrFilter::default();
-        or_filter.sqls.push(sql.into());
+        or_filter.push(None, sql);
         self.sub_filters.push(Box::new(or_filter));
         self
     }
@@ -278,16 +330,16 @@ impl<M: Arel> Filter<M> {
     /// impl Arel for User {}
     /// let mut filter = Filter::<User>::default();
     /// filter.or_not_filter("username", "sanmu");
-    /// #[cfg(any(feature = "sqlite", feature = "mysql"))]
+    /// #[cfg(feature = "sqlite")]
     /// assert_eq!(filter.to_sql().unwrap().unwrap().to_sql_string().unwrap(), r#""user"."username" != ?"#);
     ///
     /// filter.or_not_filter("aga", vec![18, 20]);
-    /// #[cfg(any(feature = "sqlite", feature = "mysql"))]
+    /// #[cfg(feature = "sqlite")]
     /// assert_eq!(filter.to_sql().unwrap().unwrap().to_sql_string().unwrap(), r#""user"."username" != ? OR "user"."aga" NOT IN (?, ?)"#);
     ///
     /// ```
-    pub fn or_not_filter<K: AsRef<str>, V: Into<crate::Value>>(&mut self, key: K, value: V) -> &mut Self {
-        self.or_not_filter_pairs(vec![(key, value)])
+    pub fn or_not_filter<K: ColumnFilter<M, V, X>, V, X>(&mut self, key: K, value: V) -> &mut Self {
+        self.or_not_filter_pairs(vec![(key.column_name(), K::filter_value(value))])
     }
     pub fn or_not_filter_pairs<K: AsRef<str>, V: Into<crate::Value>>(&mut self, pairs: Vec<(K, V)>) -> &mut Self {
         let table_name = M::table_name();
@@ -295,7 +347,7 @@ impl<M: Arel> Filter<M> {
         for (key, value) in pairs.into_iter() {
             let mut sql = crate::Sql::default();
             let value: crate::Value = value.into();
-            sql.push_str(format!(r#""{}"."{}""#, table_name, key.as_ref()));
+            sql.push_str(quote_column(&table_name, &key));
             match &value {
                 crate::Value::Array(arrary) => match arrary.deref() {
                     Some(arr) => {
@@ -313,17 +365,175 @@ impl<M: Arel> Filter<M> {
                     }
                 }
             }
-            or_filter.sqls.push(sql);
+            or_filter.push(Some(key.as_ref()), sql);
         }
         self.sub_filters.push(Box::new(or_filter));
         self
     }
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// use arel::statements::filter::Filter;
+    /// #[arel]
+    /// struct User {}
+    /// impl Arel for User {}
+    /// let mut group = Filter::<User>::default();
+    /// group.and_filter("a", 1).or_filter("b", 2);
+    /// let mut not_group = Filter::<User>::default();
+    /// not_group.and_filter("d", 4);
+    /// let mut or_group = Filter::<User>::default();
+    /// or_group.and_filter("c", 3).or_not_filter_group(not_group);
+    ///
+    /// let mut filter = Filter::<User>::default();
+    /// filter.and_filter_group(group).and_filter_group(or_group);
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(filter.to_sql().unwrap().unwrap().to_sql_string().unwrap(), r#"("user"."a" = ? OR "user"."b" = ?) AND ("user"."c" = ? OR NOT ("user"."d" = ?))"#);
+    ///
+    /// ```
+    pub fn and_filter_group(&mut self, filter: Filter<M>) -> &mut Self {
+        self.sub_filters.push(Box::new(GroupFilter::new(" AND ", false, filter.sub_filters)));
+        self
+    }
+    pub fn and_not_filter_group(&mut self, filter: Filter<M>) -> &mut Self {
+        self.sub_filters.push(Box::new(GroupFilter::new(" AND ", true, filter.sub_filters)));
+        self
+    }
+    pub fn or_filter_group(&mut self, filter: Filter<M>) -> &mut Self {
+        self.sub_filters.push(Box::new(GroupFilter::new(" OR ", false, filter.sub_filters)));
+        self
+    }
+    pub fn or_not_filter_group(&mut self, filter: Filter<M>) -> &mut Self {
+        self.sub_filters.push(Box::new(GroupFilter::new(" OR ", true, filter.sub_filters)));
+        self
+    }
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// use arel::statements::filter::{Filter, Operator};
+    /// #[arel]
+    /// struct User {}
+    /// impl Arel for User {}
+    /// let mut filter = Filter::<User>::default();
+    /// filter.and_filter_operator("age", Operator::Gt, 18).or_filter_operator("name", Operator::Like, "san%");
+    /// #[cfg(feature = "mysql")]
+    /// assert_eq!(filter.to_sql().unwrap().unwrap().to_sql_string().unwrap(), "`user`.`age` > ? OR `user`.`name` LIKE ?");
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(filter.to_sql().unwrap().unwrap().to_sql_string().unwrap(), r#""user"."age" > ? OR "user"."name" LIKE ? ESCAPE '\'"#);
+    ///
+    /// ```
+    pub fn and_filter_operator<K: ColumnFilter<M, V, X>, V, X>(&mut self, key: K, operator: Operator, value: V) -> &mut Self {
+        let mut and_filter = AndFilter::default();
+        and_filter.push(Some(key.column_name()), operator.to_sql(Self::column_sql(key.column_name()), K::filter_value(value)));
+        self.sub_filters.push(Box::new(and_filter));
+        self
+    }
+    pub fn or_filter_operator<K: ColumnFilter<M, V, X>, V, X>(&mut self, key: K, operator: Operator, value: V) -> &mut Self {
+        let mut or_filter = OrFilter::default();
+        or_filter.push(Some(key.column_name()), operator.to_sql(Self::column_sql(key.column_name()), K::filter_value(value)));
+        self.sub_filters.push(Box::new(or_filter));
+        self
+    }
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// use arel::statements::filter::Filter;
+    /// #[arel]
+    /// struct User {}
+    /// impl Arel for User {}
+    /// let mut filter = Filter::<User>::default();
+    /// filter.and_filter_between("age", 18, 30).and_not_filter_between("age", 20, 25);
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(filter.to_sql().unwrap().unwrap().to_sql_string().unwrap(), r#""user"."age" BETWEEN ? AND ? AND "user"."age" NOT BETWEEN ? AND ?"#);
+    ///
+    /// ```
+    pub fn and_filter_between<K: ColumnFilter<M, V, X>, V, X>(&mut self, key: K, start: V, end: V) -> &mut Self {
+        let mut and_filter = AndFilter::default();
+        and_filter.push(Some(key.column_name()), Self::between_sql(key.column_name(), " BETWEEN ", K::filter_value(start), K::filter_value(end)));
+        self.sub_filters.push(Box::new(and_filter));
+        self
+    }
+    pub fn and_not_filter_between<K: ColumnFilter<M, V, X>, V, X>(&mut self, key: K, start: V, end: V) -> &mut Self {
+        let mut and_filter = AndFilter::default();
+        and_filter.push(Some(key.column_name()), Self::between_sql(key.column_name(), " NOT BETWEEN ", K::filter_value(start), K::filter_value(end)));
+        self.sub_filters.push(Box::new(and_filter));
+        self
+    }
+    pub fn or_filter_between<K: ColumnFilter<M, V, X>, V, X>(&mut self, key: K, start: V, end: V) -> &mut Self {
+        let mut or_filter = OrFilter::default();
+        or_filter.push(Some(key.column_name()), Self::between_sql(key.column_name(), " BETWEEN ", K::filter_value(start), K::filter_value(end)));
+        self.sub_filters.push(Box::new(or_filter));
+        self
+    }
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// use arel::statements::filter::Filter;
+    /// #[arel]
+    /// struct User {}
+    /// impl Arel for User {}
+    /// let mut sub_sql = arel::Sql::new(r#"SELECT "wallet"."user_id" FROM "wallet" WHERE "wallet"."amount" > "#);
+    /// sub_sql.push_bind(100);
+    /// let mut filter = Filter::<User>::default();
+    /// filter.and_filter("name", "sanmu").and_filter_in_sql("id", sub_sql);
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(
+    ///     filter.to_sql().unwrap().unwrap().to_sql_string().unwrap(),
+    ///     r#""user"."name" = ? AND "user"."id" IN (SELECT "wallet"."user_id" FROM "wallet" WHERE "wallet"."amount" > ?)"#
+    /// );
+    ///
+    /// ```
+    pub fn and_filter_in_sql<K: ColumnName<M>, S: Into<crate::Sql>>(&mut self, key: K, sql: S) -> &mut Self {
+        let mut and_filter = AndFilter::default();
+        and_filter.push(Some(key.column_name()), Self::in_sql(key.column_name(), " IN (", sql.into()));
+        self.sub_filters.push(Box::new(and_filter));
+        self
+    }
+    pub fn and_not_filter_in_sql<K: ColumnName<M>, S: Into<crate::Sql>>(&mut self, key: K, sql: S) -> &mut Self {
+        let mut and_filter = AndFilter::default();
+        and_filter.push(Some(key.column_name()), Self::in_sql(key.column_name(), " NOT IN (", sql.into()));
+        self.sub_filters.push(Box::new(and_filter));
+        self
+    }
+    pub fn or_filter_in_sql<K: ColumnName<M>, S: Into<crate::Sql>>(&mut self, key: K, sql: S) -> &mut Self {
+        let mut or_filter = OrFilter::default();
+        or_filter.push(Some(key.column_name()), Self::in_sql(key.column_name(), " IN (", sql.into()));
+        self.sub_filters.push(Box::new(or_filter));
+        self
+    }
+    /// keep a failed sub query until `to_sql`, the error is returned there instead of dropping the predicate
+    pub(crate) fn and_filter_error(&mut self, error: crate::Error) -> &mut Self {
+        self.sub_filters.push(Box::new(ErrorFilter::new(error.to_string())));
+        self
+    }
+    fn in_sql(key: &str, in_str: &str, sub_sql: crate::Sql) -> crate::Sql {
+        let mut sql = crate::Sql::new(Self::column_sql(key));
+        sql.push_str(in_str).push_sql(sub_sql).push_str(")");
+        sql
+    }
+    fn between_sql(key: &str, between_str: &str, start: crate::Value, end: crate::Value) -> crate::Sql {
+        let mut sql = crate::Sql::new(Self::column_sql(key));
+        sql.push_str(between_str).push_bind(start).push_str(" AND ").push_bind(end);
+        sql
+    }
+    fn column_sql(key: &str) -> String {
+        quote_column(M::table_name(), key)
+    }
     pub fn unfilter_starts_with<K: AsRef<str>>(&mut self, start: K) -> &mut Self {
         for sub_filter in self.sub_filters.iter_mut() {
-            sub_filter.sqls_mut().retain(|sql| !sql.raw_value.starts_with(start.as_ref()));
+            sub_filter.unfilter_starts_with(start.as_ref());
         }
         self
     }
+    pub(crate) fn len(&self) -> usize {
+        self.sub_filters.len()
+    }
+    pub(crate) fn remove_leading(&mut self, count: usize) {
+        self.sub_filters.drain(..count.min(self.sub_filters.len()));
+    }
     /// # Examples
     ///
     /// ```
@@ -335,13 +545,21 @@ impl<M: Arel> Filter<M> {
     /// let mut filter = Filter::<User>::default();
     /// filter.and_filter_pairs(vec![("username", Into::<arel::Value>::into("sanmu")), ("age", Into::<arel::Value>::into(vec![18, 20]))]);
     /// filter.unfilter("age");
-    /// #[cfg(any(feature = "sqlite", feature = "mysql"))]
+    /// #[cfg(feature = "sqlite")]
     /// assert_eq!(filter.to_sql().unwrap().unwrap().to_sql_string().unwrap(), r#""user"."username" = ?"#);
     ///
+    /// // predicates wrapping the column are matched by the column too
+    /// filter.and_filter_operator("name", arel::statements::filter::Operator::ILike, "san%").and_filter_operator("age", arel::statements::filter::Operator::NullSafeEq, 18);
+    /// filter.unfilter("name").unfilter("age");
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(filter.to_sql().unwrap().unwrap().to_sql_string().unwrap(), r#""user"."username" = ?"#);
     /// ```
     pub fn unfilter<K: ToString>(&mut self, key: K) -> &mut Self {
-        let table_name = M::table_name();
-        let start_string = format!(r#""{}"."{}""#, table_name, key.to_string());
-        self.unfilter_starts_with(start_string)
+        let column = key.to_string();
+        let start = quote_column(M::table_name(), &column);
+        for sub_filter in self.sub_filters.iter_mut() {
+            sub_filter.unfilter_column(&column, &start);
+        }
+        self
     }
 }
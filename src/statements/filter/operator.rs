@@ -0,0 +1,73 @@
+use crate::db::dialect::NullSafeStyle;
+
+/// Binary predicate operators rendered by `Filter::and_filter_operator` for the current dialect.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Operator {
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    /// `LIKE`, `\` escapes `%` and `_` on every backend
+    Like,
+    NotLike,
+    /// case insensitive `LIKE`: `ILIKE` on postgres, `LOWER(..) LIKE LOWER(..)` otherwise
+    ILike,
+    NotILike,
+    /// null-safe `=`: `IS NOT DISTINCT FROM` | `<=>` | `IS`
+    NullSafeEq,
+    /// null-safe `!=`: `IS DISTINCT FROM` | `NOT (.. <=> ..)` | `IS NOT`
+    NullSafeNe,
+}
+
+impl Operator {
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::statements::filter::Operator;
+    /// let sql = Operator::Gte.to_sql(r#""user"."age""#, 18);
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(sql.to_sql_string().unwrap(), r#""user"."age" >= ?"#);
+    ///
+    /// let sql = Operator::NullSafeEq.to_sql(r#""user"."age""#, Option::<i32>::None);
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(sql.to_sql_string().unwrap(), r#""user"."age" IS ?"#);
+    /// #[cfg(feature = "mysql")]
+    /// assert_eq!(sql.to_sql_string().unwrap(), "`user`.`age` <=> ?");
+    /// #[cfg(feature = "postgres")]
+    /// assert_eq!(sql.to_sql_string().unwrap(), r#""user"."age" IS NOT DISTINCT FROM $1"#);
+    ///
+    /// let sql = Operator::ILike.to_sql(r#""user"."name""#, "sanmu%");
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(sql.to_sql_string().unwrap(), r#"LOWER("user"."name") LIKE LOWER(?) ESCAPE '\'"#);
+    /// #[cfg(feature = "postgres")]
+    /// assert_eq!(sql.to_sql_string().unwrap(), r#""user"."name" ILIKE $1"#);
+    /// ```
+    pub fn to_sql<C: AsRef<str>, V: Into<crate::Value>>(&self, column: C, value: V) -> crate::Sql {
+        let column = column.as_ref();
+        let dialect = crate::db::dialect::compiled();
+        let mut sql = crate::Sql::default();
+        match self {
+            Self::Gt => sql.push_str(format!("{} > ", column)).push_bind(value),
+            Self::Gte => sql.push_str(format!("{} >= ", column)).push_bind(value),
+            Self::Lt => sql.push_str(format!("{} < ", column)).push_bind(value),
+            Self::Lte => sql.push_str(format!("{} <= ", column)).push_bind(value),
+            Self::Like => sql.push_str(format!("{} LIKE ", column)).push_bind(value).push_str(dialect.like_escape()),
+            Self::NotLike => sql.push_str(format!("{} NOT LIKE ", column)).push_bind(value).push_str(dialect.like_escape()),
+            Self::ILike if dialect.supports_ilike() => sql.push_str(format!("{} ILIKE ", column)).push_bind(value),
+            Self::NotILike if dialect.supports_ilike() => sql.push_str(format!("{} NOT ILIKE ", column)).push_bind(value),
+            Self::ILike => sql.push_str(format!("LOWER({}) LIKE LOWER(", column)).push_bind(value).push_str(")").push_str(dialect.like_escape()),
+            Self::NotILike => sql.push_str(format!("LOWER({}) NOT LIKE LOWER(", column)).push_bind(value).push_str(")").push_str(dialect.like_escape()),
+            Self::NullSafeEq => match dialect.null_safe_style() {
+                NullSafeStyle::DistinctFrom => sql.push_str(format!("{} IS NOT DISTINCT FROM ", column)).push_bind(value),
+                NullSafeStyle::Spaceship => sql.push_str(format!("{} <=> ", column)).push_bind(value),
+                NullSafeStyle::Is => sql.push_str(format!("{} IS ", column)).push_bind(value),
+            },
+            Self::NullSafeNe => match dialect.null_safe_style() {
+                NullSafeStyle::DistinctFrom => sql.push_str(format!("{} IS DISTINCT FROM ", column)).push_bind(value),
+                NullSafeStyle::Spaceship => sql.push_str(format!("NOT ({} <=> ", column)).push_bind(value).push_str(")"),
+                NullSafeStyle::Is => sql.push_str(format!("{} IS NOT ", column)).push_bind(value),
+            },
+        };
+        sql
+    }
+}
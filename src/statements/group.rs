@@ -1,4 +1,8 @@
-use crate::{prelude::Arel, statements::ArelStatement};
+use crate::{
+    prelude::Arel,
+    sql::quote_column,
+    statements::{column::ColumnName, ArelStatement},
+};
 use std::marker::PhantomData;
 
 #[derive(Debug)]
@@ -7,6 +11,15 @@ pub struct Group<M: Arel> {
     _marker: PhantomData<M>,
 }
 
+impl<M: Arel> Clone for Group<M> {
+    fn clone(&self) -> Self {
+        Self {
+            sqls: self.sqls.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
 impl<M: Arel> ArelStatement for Group<M> {
     fn to_sql(&self) -> crate::Result<Option<crate::Sql>> {
         if self.sqls.len() > 0 {
@@ -38,13 +51,14 @@ impl<M: Arel> Group<M> {
     /// struct User {}
     /// impl Arel for User {}
     /// let group = Group::<User>::new(vec!["name", "age"]);
+    /// #[cfg(not(feature = "mysql"))]
     /// assert_eq!(group.to_sql().unwrap().unwrap().to_sql_string().unwrap(), r#"GROUP BY "user"."name", "user"."age""#);
     ///
     /// ```
-    pub fn new<T: AsRef<str>>(columns: Vec<T>) -> Self {
+    pub fn new<T: ColumnName<M>>(columns: Vec<T>) -> Self {
         let table_name = M::table_name();
         Self {
-            sqls: columns.iter().map(|column| crate::Sql::new(format!(r#""{}"."{}""#, table_name, column.as_ref()))).collect(),
+            sqls: columns.iter().map(|column| crate::Sql::new(quote_column(&table_name, column.column_name()))).collect(),
             _marker: PhantomData::<M>,
         }
     }
@@ -72,4 +86,16 @@ impl<M: Arel> Group<M> {
             _marker: PhantomData::<M>,
         }
     }
+    pub(crate) fn sqls(&self) -> &[crate::Sql] {
+        &self.sqls
+    }
+    /// Append the group columns of `other` this one does not have yet.
+    pub fn merge(&mut self, other: &Self) -> &mut Self {
+        for sql in other.sqls.iter() {
+            if !self.sqls.iter().any(|exist| exist.raw_value == sql.raw_value && exist.bind_values == sql.bind_values) {
+                self.sqls.push(sql.clone());
+            }
+        }
+        self
+    }
 }
@@ -7,6 +7,12 @@ use std::ops::{Deref, DerefMut};
 #[derive(Debug)]
 pub struct Having<M: Arel>(Filter<M>);
 
+impl<M: Arel> Clone for Having<M> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
 impl<M: Arel> ArelStatement for Having<M> {
     fn to_sql(&self) -> crate::Result<Option<crate::Sql>> {
         if let Some(filter_sql) = self.0.to_sql()? {
@@ -19,6 +25,12 @@ impl<M: Arel> ArelStatement for Having<M> {
     }
 }
 
+impl<M: Arel> Having<M> {
+    pub fn into_inner(self) -> Filter<M> {
+        self.0
+    }
+}
+
 impl<M: Arel> Deref for Having<M> {
     type Target = Filter<M>;
     fn deref(&self) -> &Self::Target {
@@ -41,7 +53,7 @@ impl<M: Arel> DerefMut for Having<M> {
 /// impl Arel for User {}
 /// let mut having = Having::<User>::default();
 /// having.and_filter("username", "sanmu");
-/// #[cfg(any(feature = "sqlite", feature = "mysql"))]
+/// #[cfg(feature = "sqlite")]
 /// assert_eq!(having.to_sql().unwrap().unwrap().to_sql_string().unwrap(), r#"HAVING "user"."username" = ?"#);
 /// ```
 impl<M: Arel> Default for Having<M> {
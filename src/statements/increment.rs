@@ -1,4 +1,5 @@
 use crate::prelude::Arel;
+use crate::sql::quote_ident;
 use crate::statements::ArelStatement;
 use std::{fmt::Debug, marker::PhantomData};
 
@@ -14,18 +15,20 @@ pub struct Increment<M: Arel> {
 impl<M: Arel> ArelStatement for Increment<M> {
     fn to_sql(&self) -> crate::Result<Option<crate::Sql>> {
         let table_name = M::table_name();
-        let mut final_sql = crate::Sql::new(format!(r#"UPDATE "{}" SET "{}" = COALESCE({}, 0) + ({})"#, table_name, self.field, self.field, self.step));
+        let mut final_sql = crate::Sql::new(format!("UPDATE {} SET {} = COALESCE({}, 0) + ({})", quote_ident(&table_name), quote_ident(&self.field), quote_ident(&self.field), self.step));
 
         final_sql.push_str(" WHERE ");
         let len = self.where_fields.len();
         for (idx, field) in self.where_fields.iter().enumerate() {
             let value = &self.where_values[idx];
-            final_sql.push_str_with_bind(format!(r#""{}" = "#, field), value.clone());
+            final_sql.push_str_with_bind(format!("{} = ", quote_ident(field)), value.clone());
             if idx < len - 1 {
                 final_sql.push_str(" AND ");
             }
         }
-        final_sql.push_str(" RETURNING *");
+        if crate::db::dialect::compiled().supports_returning() {
+            final_sql.push_str(" RETURNING *");
+        }
         Ok(Some(final_sql))
     }
 }
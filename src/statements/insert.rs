@@ -1,36 +1,186 @@
 use crate::prelude::Arel;
-use crate::statements::ArelStatement;
+use crate::sql::quote_ident;
+use crate::statements::{on_conflict::OnConflict, ArelStatement};
 use std::{fmt::Debug, marker::PhantomData};
 
+// the columns one row sets, with their values
+type BatchRow = Vec<(String, crate::Value)>;
+
+/// `INSERT INTO "table" ("a", "b") VALUES (?, ?), (?, DEFAULT) RETURNING *`, a `None` value renders `DEFAULT`.
+///
+/// Mysql has no `RETURNING`, it is left out there.
+///
+/// # Examples
+///
+/// ```
+/// use arel::prelude::*;
+/// use arel::statements::{insert::Insert, ArelStatement};
+/// #[arel]
+/// struct User {}
+/// impl Arel for User {}
+/// let insert = Insert::<User>::new_rows(vec!["name", "age"], vec![vec![Some("n1".into()), Some(18.into())], vec![Some("n2".into()), None]]);
+/// #[cfg(feature = "mysql")]
+/// assert_eq!(insert.to_sql().unwrap().unwrap().to_sql_string().unwrap(), "INSERT INTO `user` (`name`, `age`) VALUES (?, ?), (?, DEFAULT)");
+/// #[cfg(feature = "postgres")]
+/// assert_eq!(insert.returning(false).to_sql().unwrap().unwrap().to_sql_string().unwrap(), r#"INSERT INTO "user" ("name", "age") VALUES ($1, $2), ($3, DEFAULT)"#);
+/// ```
 #[derive(Debug)]
 pub struct Insert<M: Arel> {
     fields: Vec<String>,
-    values: Vec<crate::Value>,
+    rows: Vec<Vec<Option<crate::Value>>>,
+    on_conflict: Option<OnConflict>,
+    returning: bool,
     _marker: PhantomData<M>,
 }
 
 impl<M: Arel> ArelStatement for Insert<M> {
     fn to_sql(&self) -> crate::Result<Option<crate::Sql>> {
+        if self.rows.is_empty() {
+            return Ok(None);
+        }
         let table_name = M::table_name();
-        let mut final_sql = crate::Sql::new(format!(r#"INSERT INTO "{}" "#, table_name));
+        let mut final_sql = crate::Sql::new(format!("INSERT INTO {} ", quote_ident(&table_name)));
 
-        let mut field_sql = crate::Sql::default();
-        field_sql.push_str("(").push_strs(self.fields.iter().collect(), ", ").push_str(")");
-        let mut value_sql = crate::Sql::default();
-        value_sql.push_str("(").push_binds(self.values.iter().collect(), ", ").push_str(")");
-        final_sql.push_sql(field_sql).push_str(" VALUES ").push_sql(value_sql);
+        if self.fields.is_empty() && crate::db::dialect::compiled().supports_default_values() {
+            if self.rows.len() > 1 {
+                return Err(crate::Error::Message(format!("insert of {} rows without columns into {}", self.rows.len(), table_name)));
+            }
+            final_sql.push_str("DEFAULT VALUES");
+        } else {
+            let mut field_sql = crate::Sql::default();
+            field_sql.push_str("(").push_strs(self.fields.iter().map(quote_ident).collect(), ", ").push_str(")");
+            let row_sqls = self
+                .rows
+                .iter()
+                .map(|row| {
+                    let mut row_sql = crate::Sql::new("(");
+                    for (idx, value) in row.iter().enumerate() {
+                        if idx > 0 {
+                            row_sql.push_str(", ");
+                        }
+                        match value {
+                            Some(value) => row_sql.push_bind(value.clone()),
+                            None => row_sql.push_str("DEFAULT"),
+                        };
+                    }
+                    row_sql.push_str(")");
+                    row_sql
+                })
+                .collect();
+            final_sql.push_sql(field_sql).push_str(" VALUES ").push_sqls(row_sqls, ", ");
+        }
 
-        final_sql.push_str(" RETURNING *");
+        if let Some(on_conflict) = &self.on_conflict {
+            final_sql.push_str(" ").push_sql(on_conflict.to_sql(&M::primary_keys())?);
+        }
+        if self.returning {
+            final_sql.push_str(" RETURNING *");
+        }
         Ok(Some(final_sql))
     }
 }
 
 impl<M: Arel> Insert<M> {
     pub fn new<F: Into<String>, V: Into<crate::Value>>(fields: Vec<F>, values: Vec<V>) -> Self {
+        Self::new_rows(fields, vec![values.into_iter().map(|v| Some(v.into())).collect()])
+    }
+    pub fn new_rows<F: Into<String>>(fields: Vec<F>, rows: Vec<Vec<Option<crate::Value>>>) -> Self {
         Self {
             fields: fields.into_iter().map(|f| f.into()).collect(),
-            values: values.into_iter().map(|v| v.into()).collect(),
+            rows,
+            on_conflict: None,
+            returning: crate::db::dialect::compiled().supports_returning(),
             _marker: PhantomData::<M>,
         }
     }
+    /// Turn the insert into an upsert.
+    pub fn on_conflict(mut self, on_conflict: OnConflict) -> Self {
+        self.on_conflict = Some(on_conflict);
+        self
+    }
+    /// Append `RETURNING *`, on by default where the dialect supports it.
+    pub fn returning(mut self, returning: bool) -> Self {
+        self.returning = returning;
+        self
+    }
+    /// Multi-row inserts of `rows` (the column values each row sets), chunked under [`crate::db::Dialect::max_bind_params`]
+    /// together with the values bound by `on_conflict`, which every statement carries.
+    ///
+    /// Columns are the union of the columns of every row, `DEFAULT` fills the missing ones. Sqlite has no
+    /// `DEFAULT` in a VALUES list, consecutive rows setting the same columns share a statement there instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// use arel::statements::{insert::Insert, ArelStatement};
+    /// #[arel]
+    /// struct User {}
+    /// impl Arel for User {}
+    /// let rows = vec![vec![("name", "n1".into())], vec![("name", "n2".into())], vec![("name", "n3".into()), ("age", 18.into())]];
+    /// let sqls: Vec<String> = Insert::<User>::batches(rows, None).iter().map(|insert| insert.to_sql().unwrap().unwrap().to_sql_string().unwrap()).collect();
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(sqls, vec![r#"INSERT INTO "user" ("name") VALUES (?), (?) RETURNING *"#, r#"INSERT INTO "user" ("name", "age") VALUES (?, ?) RETURNING *"#]);
+    /// #[cfg(feature = "mysql")]
+    /// assert_eq!(sqls, vec!["INSERT INTO `user` (`name`, `age`) VALUES (?, DEFAULT), (?, DEFAULT), (?, ?)"]);
+    ///
+    /// // the value bound by the update set leaves room for one row less per statement
+    /// let max_bind_params = arel::db::dialect::compiled().max_bind_params();
+    /// let rows = (0..max_bind_params).map(|idx| vec![("name", arel::Value::from(idx.to_string()))]).collect();
+    /// let on_conflict = arel::statements::OnConflict::columns(vec!["name"]).set("visits", 1);
+    /// assert_eq!(Insert::<User>::batches(rows, Some(&on_conflict)).len(), 2);
+    /// ```
+    pub fn batches<F: Into<String>>(rows: Vec<Vec<(F, crate::Value)>>, on_conflict: Option<&OnConflict>) -> Vec<Self> {
+        let dialect = crate::db::dialect::compiled();
+        let rows: Vec<BatchRow> = rows.into_iter().map(|row| row.into_iter().map(|(field, value)| (field.into(), value)).collect()).collect();
+        let mut groups: Vec<(Vec<String>, Vec<BatchRow>)> = vec![];
+        for row in rows {
+            let row_fields: Vec<String> = row.iter().map(|(field, _)| field.clone()).collect();
+            match groups.last_mut() {
+                Some((fields, group_rows)) if dialect.supports_default_in_values() => {
+                    for field in row_fields {
+                        if !fields.contains(&field) {
+                            fields.push(field);
+                        }
+                    }
+                    group_rows.push(row);
+                }
+                Some((fields, group_rows)) if *fields == row_fields => group_rows.push(row),
+                _ => groups.push((row_fields, vec![row])),
+            }
+        }
+
+        // an invalid on_conflict binds nothing, its error comes back from `to_sql`
+        let conflict_binds = on_conflict.and_then(|on_conflict| on_conflict.to_sql(&M::primary_keys()).ok()).map(|sql| sql.bind_values.len()).unwrap_or_default();
+        let max_bind_params = dialect.max_bind_params().saturating_sub(conflict_binds);
+        let mut batches = vec![];
+        for (fields, group_rows) in groups {
+            let chunk_size = if fields.is_empty() {
+                if !dialect.supports_default_values() {
+                    max_bind_params.max(1)
+                } else {
+                    1
+                }
+            } else {
+                (max_bind_params / fields.len()).max(1)
+            };
+            let mut group_rows = group_rows.into_iter().peekable();
+            while group_rows.peek().is_some() {
+                let chunk = group_rows
+                    .by_ref()
+                    .take(chunk_size)
+                    .map(|mut row| {
+                        fields
+                            .iter()
+                            .map(|field| row.iter().position(|(row_field, _)| row_field == field).map(|idx| row.swap_remove(idx).1))
+                            .collect()
+                    })
+                    .collect();
+                let mut insert = Self::new_rows(fields.clone(), chunk);
+                insert.on_conflict = on_conflict.cloned();
+                batches.push(insert);
+            }
+        }
+        batches
+    }
 }
@@ -1,4 +1,8 @@
-use crate::{statements::ArelStatement, Arel};
+use crate::{
+    sql::{quote_column, quote_ident},
+    statements::ArelStatement,
+    Arel,
+};
 use std::marker::PhantomData;
 
 pub enum JoinConst {
@@ -26,6 +30,15 @@ pub struct Join<M: Arel> {
     _marker: PhantomData<M>,
 }
 
+impl<M: Arel> Clone for Join<M> {
+    fn clone(&self) -> Self {
+        Self {
+            sqls: self.sqls.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
 impl<M: Arel> ArelStatement for Join<M> {
     fn to_sql(&self) -> crate::Result<Option<crate::Sql>> {
         if self.sqls.len() > 0 {
@@ -61,6 +74,7 @@ impl<M: Arel> Join<M> {
     /// impl Arel for Wallet {}
     /// let mut join = Join::<User>::default();
     /// join.join::<Wallet>(arel::JoinConst::InnerJoin);
+    /// #[cfg(not(feature = "mysql"))]
     /// assert_eq!(join.to_sql().unwrap().unwrap().to_sql_string().unwrap(), r#"INNER JOIN "wallet" ON "user"."id" = "wallet"."user_id""#);
     ///
     /// #[arel]
@@ -73,6 +87,7 @@ impl<M: Arel> Join<M> {
     /// impl Arel for Admin {}
     /// let mut join = Join::<Admin>::default();
     /// join.join::<Wallet>(arel::JoinConst::InnerJoin);
+    /// #[cfg(not(feature = "mysql"))]
     /// assert_eq!(join.to_sql().unwrap().unwrap().to_sql_string().unwrap(), r#"INNER JOIN "wallet" ON "admin"."id" = "wallet"."admin_id" AND "admin"."uuid" = "wallet"."admin_uuid""#);
     ///
     /// ```
@@ -80,13 +95,13 @@ impl<M: Arel> Join<M> {
         let m_table_name = M::table_name();
         let u_table_name = U::table_name();
         let m_primary_keys = M::primary_keys();
-        let mut sql = crate::Sql::new(format!(r#"{} "{}" ON "#, join_type.to_string(), u_table_name));
+        let mut sql = crate::Sql::new(format!("{} {} ON ", join_type, quote_ident(&u_table_name)));
         for (idx, m_primary_key) in m_primary_keys.iter().enumerate() {
             if idx >= 1 {
                 sql.push_str(" AND ");
             }
             let u_m_foreign_key = format!("{}_{}", m_table_name, m_primary_key);
-            sql.push_str(format!(r#""{}"."{}" = "{}"."{}""#, m_table_name, m_primary_key, u_table_name, u_m_foreign_key));
+            sql.push_str(format!("{} = {}", quote_column(&m_table_name, m_primary_key), quote_column(&u_table_name, u_m_foreign_key)));
         }
         self.sqls.push(sql);
         self
@@ -109,4 +124,19 @@ impl<M: Arel> Join<M> {
         self.sqls.push(sql.into());
         self
     }
+    pub(crate) fn len(&self) -> usize {
+        self.sqls.len()
+    }
+    pub(crate) fn remove_leading(&mut self, count: usize) {
+        self.sqls.drain(..count.min(self.sqls.len()));
+    }
+    /// Append the joins of `other` this one does not have yet.
+    pub fn merge(&mut self, other: &Self) -> &mut Self {
+        for sql in other.sqls.iter() {
+            if !self.sqls.iter().any(|exist| exist.raw_value == sql.raw_value && exist.bind_values == sql.bind_values) {
+                self.sqls.push(sql.clone());
+            }
+        }
+        self
+    }
 }
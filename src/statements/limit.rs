@@ -1,6 +1,6 @@
 use crate::statements::ArelStatement;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Limit {
     num: usize,
 }
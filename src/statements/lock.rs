@@ -1,18 +1,129 @@
+use crate::sql::quote_ident;
 use crate::statements::ArelStatement;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LockConst {
+    /// `FOR UPDATE`
+    Update,
+    /// `FOR NO KEY UPDATE`, mysql has no such mode and takes `FOR UPDATE` instead
+    NoKeyUpdate,
+    /// `FOR SHARE`
+    Share,
+}
+impl std::fmt::Display for LockConst {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            LockConst::Update => write!(f, "FOR UPDATE"),
+            LockConst::NoKeyUpdate => {
+                if crate::db::dialect::compiled().supports_no_key_update() {
+                    write!(f, "FOR NO KEY UPDATE")
+                } else {
+                    write!(f, "FOR UPDATE")
+                }
+            }
+            LockConst::Share => write!(f, "FOR SHARE"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LockWaitConst {
+    /// `NOWAIT`: fail at once when a row is locked
+    NoWait,
+    /// `SKIP LOCKED`: leave locked rows out of the result
+    SkipLocked,
+}
+impl std::fmt::Display for LockWaitConst {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            LockWaitConst::NoWait => write!(f, "NOWAIT"),
+            LockWaitConst::SkipLocked => write!(f, "SKIP LOCKED"),
+        }
+    }
+}
+
+/// Row locking clause: `FOR UPDATE | FOR NO KEY UPDATE | FOR SHARE [OF "table", ..] [NOWAIT | SKIP LOCKED]`.
+///
+/// Sqlite has no row locks, rendering returns an error there instead of invalid sql:
+/// run the statements in a transaction, sqlite locks the whole database on the first write.
+///
+/// # Examples
+///
+/// ```
+/// use arel::statements::{lock::Lock, ArelStatement};
+/// let lock = Lock::share().of("user").skip_locked();
+/// #[cfg(feature = "mysql")]
+/// assert_eq!(lock.to_sql().unwrap().unwrap().to_sql_string().unwrap(), "FOR SHARE OF `user` SKIP LOCKED");
+/// #[cfg(feature = "postgres")]
+/// assert_eq!(lock.to_sql().unwrap().unwrap().to_sql_string().unwrap(), r#"FOR SHARE OF "user" SKIP LOCKED"#);
+/// #[cfg(feature = "sqlite")]
+/// assert!(lock.to_sql().is_err());
+/// ```
+#[derive(Debug, Clone)]
 pub struct Lock {
-    value: String,
+    mode: LockConst,
+    tables: Vec<String>,
+    wait: Option<LockWaitConst>,
 }
 
 impl ArelStatement for Lock {
     fn to_sql(&self) -> crate::Result<Option<crate::Sql>> {
-        Ok(Some(crate::Sql::new(&self.value)))
+        let dialect = crate::db::dialect::compiled();
+        if !dialect.supports_row_locks() {
+            return Err(crate::Error::Message(format!("{} does not support row locking ({}), run the statements in a transaction instead", dialect.name().to_lowercase(), self.mode)));
+        }
+        let mut final_sql = crate::Sql::new(self.mode);
+        if !self.tables.is_empty() {
+            let tables = self.tables.iter().map(quote_ident).collect();
+            final_sql.push_str(" OF ").push_strs(tables, ", ");
+        }
+        if let Some(wait) = &self.wait {
+            final_sql.push_str(format!(" {}", wait));
+        }
+        Ok(Some(final_sql))
+    }
+}
+
+impl Default for Lock {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 impl Lock {
+    /// `FOR UPDATE`
     pub fn new() -> Self {
-        Self { value: "FOR UPDATE".to_string() }
+        Self::with_mode(LockConst::Update)
+    }
+    pub fn with_mode(mode: LockConst) -> Self {
+        Self { mode, tables: vec![], wait: None }
+    }
+    /// `FOR SHARE`
+    pub fn share() -> Self {
+        Self::with_mode(LockConst::Share)
+    }
+    /// `FOR NO KEY UPDATE`
+    pub fn no_key_update() -> Self {
+        Self::with_mode(LockConst::NoKeyUpdate)
+    }
+    pub fn mode(mut self, mode: LockConst) -> Self {
+        self.mode = mode;
+        self
+    }
+    /// lock only the rows of `table`, for queries with joins
+    pub fn of<T: ToString>(mut self, table: T) -> Self {
+        let table = table.to_string();
+        if !self.tables.contains(&table) {
+            self.tables.push(table);
+        }
+        self
+    }
+    pub fn nowait(mut self) -> Self {
+        self.wait = Some(LockWaitConst::NoWait);
+        self
+    }
+    pub fn skip_locked(mut self) -> Self {
+        self.wait = Some(LockWaitConst::SkipLocked);
+        self
     }
 }
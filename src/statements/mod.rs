@@ -1,6 +1,8 @@
+pub mod column;
 pub mod filter;
 
 pub mod delete;
+pub mod expr;
 pub mod group;
 pub mod having;
 pub mod increment;
@@ -9,12 +11,16 @@ pub mod join;
 pub mod limit;
 pub mod lock;
 pub mod offset;
+pub mod on_conflict;
 pub mod order;
 pub mod select;
 pub mod update;
 pub mod r#where;
+pub mod with;
 
+pub use column::Column;
 pub use delete::Delete;
+pub use expr::Expr;
 pub use group::Group;
 pub use having::Having;
 pub use increment::Increment;
@@ -23,10 +29,12 @@ pub use join::Join;
 pub use limit::Limit;
 pub use lock::Lock;
 pub use offset::Offset;
+pub use on_conflict::OnConflict;
 pub use order::Order;
 pub use r#where::Where;
 pub use select::Select;
 pub use update::Update;
+pub use with::With;
 
 pub trait ArelStatement {
     fn to_sql(&self) -> crate::Result<Option<crate::Sql>>;
@@ -0,0 +1,163 @@
+use crate::db::dialect::UpsertStyle;
+use crate::sql::quote_ident;
+use crate::statements::expr::Expr;
+
+#[derive(Debug, Clone)]
+pub enum ConflictTarget {
+    /// `ON CONFLICT ("a", "b")`
+    Columns(Vec<String>),
+    /// `ON CONFLICT ON CONSTRAINT "name"`, postgres only
+    Constraint(String),
+}
+
+#[derive(Debug, Clone)]
+pub enum ConflictAction {
+    Nothing,
+    Update(Vec<(String, Expr)>),
+}
+
+/// Conflict handling of an [`crate::statements::insert::Insert`]: `ON CONFLICT .. DO UPDATE SET | DO NOTHING`
+/// on sqlite and postgres, `ON DUPLICATE KEY UPDATE` on mysql.
+///
+/// Mysql takes no conflict target, any unique key conflicts there, `DO NOTHING` renders as a no-op update of
+/// the first conflict column (the first primary key without columns) so other errors are not ignored.
+///
+/// # Examples
+///
+/// ```
+/// use arel::statements::{expr::Expr, on_conflict::OnConflict};
+/// let on_conflict = OnConflict::columns(vec!["email"]).update_excluded(vec!["name"]).set("login_count", Expr::raw(r#""login_count" + 1"#));
+/// #[cfg(any(feature = "sqlite", feature = "postgres"))]
+/// assert_eq!(
+///     on_conflict.to_sql(&["id"]).unwrap().to_sql_string().unwrap(),
+///     r#"ON CONFLICT ("email") DO UPDATE SET "name" = excluded."name", "login_count" = "login_count" + 1"#
+/// );
+/// #[cfg(feature = "mysql")]
+/// assert_eq!(on_conflict.to_sql(&["id"]).unwrap().to_sql_string().unwrap(), "ON DUPLICATE KEY UPDATE `name` = VALUES(`name`), `login_count` = `login_count` + 1");
+///
+/// let on_conflict = OnConflict::columns(vec!["email"]).do_nothing();
+/// #[cfg(any(feature = "sqlite", feature = "postgres"))]
+/// assert_eq!(on_conflict.to_sql(&["id"]).unwrap().to_sql_string().unwrap(), r#"ON CONFLICT ("email") DO NOTHING"#);
+/// #[cfg(feature = "mysql")]
+/// assert_eq!(on_conflict.to_sql(&["id"]).unwrap().to_sql_string().unwrap(), "ON DUPLICATE KEY UPDATE `email` = `email`");
+///
+/// // an update set needs a conflict target outside mysql
+/// let on_conflict = OnConflict::any().update_excluded(vec!["name"]);
+/// #[cfg(any(feature = "sqlite", feature = "postgres"))]
+/// assert!(on_conflict.to_sql(&["id"]).is_err());
+/// #[cfg(feature = "mysql")]
+/// assert_eq!(on_conflict.to_sql(&["id"]).unwrap().to_sql_string().unwrap(), "ON DUPLICATE KEY UPDATE `name` = VALUES(`name`)");
+/// ```
+#[derive(Debug, Clone)]
+pub struct OnConflict {
+    target: Option<ConflictTarget>,
+    action: ConflictAction,
+}
+
+impl OnConflict {
+    /// Conflicts on the unique index over `columns`.
+    pub fn columns<T: ToString>(columns: Vec<T>) -> Self {
+        Self::with_target(Some(ConflictTarget::Columns(columns.into_iter().map(|column| column.to_string()).collect())))
+    }
+    /// Conflicts on a named constraint, postgres only.
+    pub fn constraint<T: ToString>(name: T) -> Self {
+        Self::with_target(Some(ConflictTarget::Constraint(name.to_string())))
+    }
+    /// Conflicts on any unique index, only `DO NOTHING` outside mysql: an update set needs a target there.
+    pub fn any() -> Self {
+        Self::with_target(None)
+    }
+    fn with_target(target: Option<ConflictTarget>) -> Self {
+        Self {
+            target,
+            action: ConflictAction::Nothing,
+        }
+    }
+    /// `DO NOTHING`, the default action.
+    pub fn do_nothing(mut self) -> Self {
+        self.action = ConflictAction::Nothing;
+        self
+    }
+    /// `"column" = expr` in the update set, plain values are bound.
+    pub fn set<T: ToString, E: crate::statements::expr::AssignValue>(mut self, column: T, expr: E) -> Self {
+        let assignment = (column.to_string(), expr.into_expr());
+        match &mut self.action {
+            ConflictAction::Update(assignments) => assignments.push(assignment),
+            ConflictAction::Nothing => self.action = ConflictAction::Update(vec![assignment]),
+        }
+        self
+    }
+    /// Overwrite `columns` with the values of the row that failed to insert, see [`Expr::excluded`].
+    pub fn update_excluded<T: ToString>(mut self, columns: Vec<T>) -> Self {
+        for column in columns {
+            let column = column.to_string();
+            self = self.set(column.clone(), Expr::excluded(column));
+        }
+        self
+    }
+    /// The columns of the conflict target, empty for a constraint or [`OnConflict::any`].
+    pub fn target_columns(&self) -> Vec<String> {
+        match &self.target {
+            Some(ConflictTarget::Columns(columns)) => columns.clone(),
+            _ => vec![],
+        }
+    }
+    /// `primary_keys` of the inserted model are the no-op update column of `DO NOTHING` on mysql without a target column.
+    pub fn to_sql(&self, primary_keys: &[&str]) -> crate::Result<crate::Sql> {
+        let dialect = crate::db::dialect::compiled();
+        if dialect.upsert_style() == UpsertStyle::OnDuplicateKey {
+            let mut final_sql = crate::Sql::new("ON DUPLICATE KEY UPDATE ");
+            match &self.action {
+                ConflictAction::Update(assignments) => {
+                    final_sql.push_sqls(Self::assignment_sqls(assignments), ", ");
+                }
+                ConflictAction::Nothing => {
+                    let column = match (self.target_columns().first(), primary_keys.first()) {
+                        (Some(column), _) => column.clone(),
+                        (None, Some(primary_key)) => primary_key.to_string(),
+                        (None, None) => return Err(crate::Error::Message("DO NOTHING on mysql needs a conflict column or a primary key".to_string())),
+                    };
+                    final_sql.push_str(format!("{} = {}", quote_ident(&column), quote_ident(&column)));
+                }
+            }
+            return Ok(final_sql);
+        }
+
+        let mut final_sql = crate::Sql::new("ON CONFLICT ");
+        match &self.target {
+            Some(ConflictTarget::Columns(columns)) => {
+                final_sql.push_str("(").push_strs(columns.iter().map(quote_ident).collect(), ", ").push_str(") ");
+            }
+            Some(ConflictTarget::Constraint(name)) => {
+                if !dialect.supports_conflict_constraint() {
+                    return Err(crate::Error::Message(format!("{} does not support ON CONFLICT ON CONSTRAINT \"{}\", use the constraint columns instead", dialect.name().to_lowercase(), name)));
+                }
+                final_sql.push_str(format!("ON CONSTRAINT {} ", quote_ident(name)));
+            }
+            None => {
+                if let ConflictAction::Update(_) = self.action {
+                    return Err(crate::Error::Message(format!("{} needs a conflict target to DO UPDATE, use OnConflict::columns", dialect.name().to_lowercase())));
+                }
+            }
+        }
+        match &self.action {
+            ConflictAction::Update(assignments) => {
+                final_sql.push_str("DO UPDATE SET ").push_sqls(Self::assignment_sqls(assignments), ", ");
+            }
+            ConflictAction::Nothing => {
+                final_sql.push_str("DO NOTHING");
+            }
+        }
+        Ok(final_sql)
+    }
+    fn assignment_sqls(assignments: &[(String, Expr)]) -> Vec<crate::Sql> {
+        assignments
+            .iter()
+            .map(|(column, expr)| {
+                let mut sql = crate::Sql::new(format!("{} = ", quote_ident(column)));
+                sql.push_sql(expr.clone().into_sql());
+                sql
+            })
+            .collect()
+    }
+}
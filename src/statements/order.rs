@@ -1,10 +1,23 @@
-use crate::{prelude::Arel, statements::ArelStatement};
+use crate::{
+    prelude::Arel,
+    sql::quote_column,
+    statements::{column::ColumnName, ArelStatement},
+};
 use std::marker::PhantomData;
 
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum SortConst {
     Asc,
     Desc,
 }
+impl SortConst {
+    pub fn reverse(&self) -> Self {
+        match self {
+            SortConst::Asc => SortConst::Desc,
+            SortConst::Desc => SortConst::Asc,
+        }
+    }
+}
 impl std::fmt::Display for SortConst {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
@@ -14,17 +27,107 @@ impl std::fmt::Display for SortConst {
     }
 }
 
+/// Where nulls go, rendered as `NULLS FIRST | NULLS LAST` on postgres and emulated with a leading `expr IS NULL` order on mysql and sqlite.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NullsConst {
+    First,
+    Last,
+}
+impl NullsConst {
+    pub fn reverse(&self) -> Self {
+        match self {
+            NullsConst::First => NullsConst::Last,
+            NullsConst::Last => NullsConst::First,
+        }
+    }
+}
+impl std::fmt::Display for NullsConst {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            NullsConst::First => write!(f, "NULLS FIRST"),
+            NullsConst::Last => write!(f, "NULLS LAST"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum OrderItem {
+    // column of the table itself, or of `table` for joins
+    Column { table: Option<String>, column: String, sort_type: SortConst, nulls: Option<NullsConst> },
+    Expr { sql: crate::Sql, sort_type: SortConst, nulls: Option<NullsConst> },
+    Sql(crate::Sql),
+    // raw sql asked to be reversed
+    Irreversible(crate::Sql),
+}
+
+impl OrderItem {
+    fn reverse(self) -> Self {
+        match self {
+            OrderItem::Column { table, column, sort_type, nulls } => OrderItem::Column {
+                table,
+                column,
+                sort_type: sort_type.reverse(),
+                nulls: nulls.map(|nulls| nulls.reverse()),
+            },
+            OrderItem::Expr { sql, sort_type, nulls } => OrderItem::Expr {
+                sql,
+                sort_type: sort_type.reverse(),
+                nulls: nulls.map(|nulls| nulls.reverse()),
+            },
+            OrderItem::Sql(sql) | OrderItem::Irreversible(sql) => OrderItem::Irreversible(sql),
+        }
+    }
+    fn to_sql(&self, table_name: &str) -> crate::Result<crate::Sql> {
+        let (target, sort_type, nulls) = match self {
+            OrderItem::Column { table, column, sort_type, nulls } => {
+                let target = crate::Sql::new(quote_column(table.as_deref().unwrap_or(table_name), column));
+                (target, sort_type, nulls)
+            }
+            OrderItem::Expr { sql, sort_type, nulls } => (sql.clone(), sort_type, nulls),
+            OrderItem::Sql(sql) => return Ok(sql.clone()),
+            OrderItem::Irreversible(sql) => return Err(crate::Error::Message(format!("order {} is raw sql and can not be reversed", sql.to_sql_string()?))),
+        };
+        let mut sql = crate::Sql::default();
+        match nulls {
+            Some(nulls) if !crate::db::dialect::compiled().supports_nulls_ordering() => {
+                // `expr IS NULL` is 1 for nulls, sorting it first puts them before or after every other row
+                let nulls_sort_type = if *nulls == NullsConst::First { SortConst::Desc } else { SortConst::Asc };
+                sql.push_sql(target.clone()).push_str(format!(" IS NULL {}, ", nulls_sort_type));
+                sql.push_sql(target).push_str(format!(" {}", sort_type));
+            }
+            Some(nulls) => {
+                sql.push_sql(target).push_str(format!(" {} {}", sort_type, nulls));
+            }
+            None => {
+                sql.push_sql(target).push_str(format!(" {}", sort_type));
+            }
+        }
+        Ok(sql)
+    }
+}
+
 #[derive(Debug)]
 pub struct Order<M: Arel> {
-    sqls: Vec<crate::Sql>,
+    items: Vec<OrderItem>,
     _marker: PhantomData<M>,
 }
 
+impl<M: Arel> Clone for Order<M> {
+    fn clone(&self) -> Self {
+        Self {
+            items: self.items.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
 impl<M: Arel> ArelStatement for Order<M> {
     fn to_sql(&self) -> crate::Result<Option<crate::Sql>> {
-        if self.sqls.len() > 0 {
+        if self.items.len() > 0 {
+            let table_name = M::table_name();
+            let sqls = self.items.iter().map(|item| item.to_sql(&table_name)).collect::<crate::Result<Vec<crate::Sql>>>()?;
             let mut final_sql = crate::Sql::new("ORDER BY ");
-            final_sql.push_sqls(self.sqls.clone(), ", ");
+            final_sql.push_sqls(sqls, ", ");
             Ok(Some(final_sql))
         } else {
             Ok(None)
@@ -35,7 +138,7 @@ impl<M: Arel> ArelStatement for Order<M> {
 impl<M: Arel> Default for Order<M> {
     fn default() -> Self {
         Self {
-            sqls: vec![],
+            items: vec![],
             _marker: PhantomData::<M>,
         }
     }
@@ -51,10 +154,11 @@ impl<M: Arel> Order<M> {
     /// struct User {}
     /// impl Arel for User {}
     /// let order = Order::<User>::new("name", arel::SortConst::Desc);
+    /// #[cfg(not(feature = "mysql"))]
     /// assert_eq!(order.to_sql().unwrap().unwrap().to_sql_string().unwrap(), r#"ORDER BY "user"."name" DESC"#);
     ///
     /// ```
-    pub fn new<T: AsRef<str>>(column: T, sort_type: SortConst) -> Self {
+    pub fn new<T: ColumnName<M>>(column: T, sort_type: SortConst) -> Self {
         Self::new_columns(vec![(column, sort_type)])
     }
     /// # Examples
@@ -66,22 +170,19 @@ impl<M: Arel> Order<M> {
     /// struct User {}
     /// impl Arel for User {}
     /// let order = Order::<User>::new_columns(vec![("name", arel::SortConst::Desc), ("age", arel::SortConst::Asc)]);
+    /// #[cfg(not(feature = "mysql"))]
     /// assert_eq!(order.to_sql().unwrap().unwrap().to_sql_string().unwrap(), r#"ORDER BY "user"."name" DESC, "user"."age" ASC"#);
     ///
     /// ```
-    pub fn new_columns<T: AsRef<str>>(columns: Vec<(T, SortConst)>) -> Self {
-        let table_name = M::table_name();
+    pub fn new_columns<T: ColumnName<M>>(columns: Vec<(T, SortConst)>) -> Self {
         Self {
-            sqls: columns
-                .iter()
-                .map(|column| crate::Sql::new(format!(r#""{}"."{}" {}"#, table_name, column.0.as_ref(), column.1.to_string())))
-                .collect(),
+            items: columns.into_iter().map(|(column, sort_type)| Self::column_item(None, column.column_name(), sort_type, None)).collect(),
             _marker: PhantomData::<M>,
         }
     }
     pub fn new_sql<S: Into<crate::Sql>>(sql: S) -> Self {
         Self {
-            sqls: vec![sql.into()],
+            items: vec![OrderItem::Sql(sql.into())],
             _marker: PhantomData::<M>,
         }
     }
@@ -99,17 +200,135 @@ impl<M: Arel> Order<M> {
     /// ```
     pub fn new_sqls<S: Into<crate::Sql>>(sqls: Vec<S>) -> Self {
         Self {
-            sqls: sqls.into_iter().map(|sql| sql.into()).collect(),
+            items: sqls.into_iter().map(|sql| OrderItem::Sql(sql.into())).collect(),
             _marker: PhantomData::<M>,
         }
     }
-    pub fn append<T: AsRef<str>>(&mut self, column: T, sort_type: SortConst) -> &mut Self {
-        let table_name = M::table_name();
-        self.sqls.push(crate::Sql::new(format!(r#""{}"."{}" {}"#, table_name, column.as_ref(), sort_type.to_string())));
+    pub fn append<T: ColumnName<M>>(&mut self, column: T, sort_type: SortConst) -> &mut Self {
+        self.items.push(Self::column_item(None, column.column_name(), sort_type, None));
+        self
+    }
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// use arel::statements::order::{NullsConst, Order};
+    /// #[arel]
+    /// struct User {}
+    /// impl Arel for User {}
+    /// let mut order = Order::<User>::default();
+    /// order.append_nulls("age", arel::SortConst::Asc, NullsConst::Last);
+    /// #[cfg(feature = "postgres")]
+    /// assert_eq!(order.to_sql().unwrap().unwrap().to_sql_string().unwrap(), r#"ORDER BY "user"."age" ASC NULLS LAST"#);
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(order.to_sql().unwrap().unwrap().to_sql_string().unwrap(), r#"ORDER BY "user"."age" IS NULL ASC, "user"."age" ASC"#);
+    /// ```
+    pub fn append_nulls<T: ColumnName<M>>(&mut self, column: T, sort_type: SortConst, nulls: NullsConst) -> &mut Self {
+        self.items.push(Self::column_item(None, column.column_name(), sort_type, Some(nulls)));
+        self
+    }
+    /// A column of a joined table.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// use arel::statements::order::Order;
+    /// #[arel]
+    /// struct User {}
+    /// impl Arel for User {}
+    /// #[arel]
+    /// struct Wallet {}
+    /// impl Arel for Wallet {}
+    /// let mut order = Order::<User>::default();
+    /// order.append_joined::<Wallet, _>("amount", arel::SortConst::Desc, None).append("id", arel::SortConst::Asc);
+    /// #[cfg(not(feature = "mysql"))]
+    /// assert_eq!(order.to_sql().unwrap().unwrap().to_sql_string().unwrap(), r#"ORDER BY "wallet"."amount" DESC, "user"."id" ASC"#);
+    /// ```
+    pub fn append_joined<U: Arel, T: ColumnName<U>>(&mut self, column: T, sort_type: SortConst, nulls: Option<NullsConst>) -> &mut Self {
+        self.items.push(Self::column_item(Some(U::table_name()), column.column_name(), sort_type, nulls));
+        self
+    }
+    /// An expression, its binds are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// use arel::statements::{expr::Expr, order::Order};
+    /// #[arel]
+    /// struct User {}
+    /// impl Arel for User {}
+    /// let mut order = Order::<User>::default();
+    /// order.append_expr(Expr::case().when(Expr::raw(r#""user"."type""#).eq(Expr::value("ADMIN")), 0).otherwise(1), arel::SortConst::Asc, None);
+    /// #[cfg(feature = "sqlite")]
+    /// assert_eq!(order.to_sql().unwrap().unwrap().to_sql_string().unwrap(), r#"ORDER BY CASE WHEN "user"."type" = ? THEN ? ELSE ? END ASC"#);
+    /// ```
+    pub fn append_expr<E: Into<crate::statements::expr::Expr>>(&mut self, expr: E, sort_type: SortConst, nulls: Option<NullsConst>) -> &mut Self {
+        self.items.push(OrderItem::Expr {
+            sql: expr.into().into_sql(),
+            sort_type,
+            nulls,
+        });
         self
     }
     pub fn append_sql<S: Into<crate::Sql>>(&mut self, sql: S) -> &mut Self {
-        self.sqls.push(sql.into());
+        self.items.push(OrderItem::Sql(sql.into()));
         self
     }
+    /// Flip every direction (and nulls placement), raw sql orders fail to render once reversed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// use arel::statements::order::{NullsConst, Order};
+    /// #[arel]
+    /// struct User {}
+    /// impl Arel for User {}
+    /// let mut order = Order::<User>::new("name", arel::SortConst::Desc);
+    /// order.append_nulls("age", arel::SortConst::Asc, NullsConst::First).reverse();
+    /// #[cfg(feature = "postgres")]
+    /// assert_eq!(order.to_sql().unwrap().unwrap().to_sql_string().unwrap(), r#"ORDER BY "user"."name" ASC, "user"."age" DESC NULLS LAST"#);
+    ///
+    /// let mut order = Order::<User>::new_sql("name DESC");
+    /// order.reverse();
+    /// assert!(order.to_sql().is_err());
+    /// ```
+    pub fn reverse(&mut self) -> &mut Self {
+        self.items = std::mem::take(&mut self.items).into_iter().map(|item| item.reverse()).collect();
+        self
+    }
+    /// Append the orders of `other` after the ones of this order.
+    pub fn merge(&mut self, other: &Self) -> &mut Self {
+        self.items.extend(other.items.iter().cloned());
+        self
+    }
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+    pub(crate) fn len(&self) -> usize {
+        self.items.len()
+    }
+    pub(crate) fn remove_leading(&mut self, count: usize) {
+        self.items.drain(..count.min(self.items.len()));
+    }
+    /// The ordered columns of the table itself with their nulls placement, `None` when any order is raw sql, an expression or a joined column.
+    pub fn columns(&self) -> Option<Vec<(&str, SortConst, Option<NullsConst>)>> {
+        self.items
+            .iter()
+            .map(|item| match item {
+                OrderItem::Column { table: None, column, sort_type, nulls } => Some((column.as_str(), *sort_type, *nulls)),
+                _ => None,
+            })
+            .collect()
+    }
+    fn column_item(table: Option<String>, column: &str, sort_type: SortConst, nulls: Option<NullsConst>) -> OrderItem {
+        OrderItem::Column {
+            table,
+            column: column.to_string(),
+            sort_type,
+            nulls,
+        }
+    }
 }
@@ -1,15 +1,37 @@
-use crate::{statements::ArelStatement, Arel};
+use crate::{
+    sql::{quote_column, quote_ident},
+    statements::{column::ColumnName, ArelStatement},
+    Arel,
+};
 use std::marker::PhantomData;
 
 #[derive(Debug)]
 pub struct Select<M: Arel> {
     distinct: bool,
     sqls: Vec<crate::Sql>,
+    from: Option<crate::Sql>,
     _marker: PhantomData<M>,
 }
 
+impl<M: Arel> Clone for Select<M> {
+    fn clone(&self) -> Self {
+        Self {
+            distinct: self.distinct,
+            sqls: self.sqls.clone(),
+            from: self.from.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
 impl<M: Arel> ArelStatement for Select<M> {
     fn to_sql(&self) -> crate::Result<Option<crate::Sql>> {
+        Ok(Some(self.render()))
+    }
+}
+
+impl<M: Arel> Select<M> {
+    fn render(&self) -> crate::Sql {
         let mut final_sql = crate::Sql::new("SELECT ");
         if self.distinct {
             final_sql.push_str("DISTINCT ");
@@ -18,10 +40,14 @@ impl<M: Arel> ArelStatement for Select<M> {
         if self.sqls.len() > 0 {
             final_sql.push_sqls(self.sqls.clone(), ", ");
         } else {
-            final_sql.push_str(format!(r#""{}".*"#, M::table_name()));
+            final_sql.push_str(quote_column(M::table_name(), "*"));
+        }
+        if let Some(from) = &self.from {
+            final_sql.push_str(" FROM ").push_sql(from.clone());
+        } else {
+            final_sql.push_str(format!(" FROM {}", quote_ident(M::table_name())));
         }
-        final_sql.push_str(format!(r#" FROM "{}""#, M::table_name()));
-        Ok(Some(final_sql))
+        final_sql
     }
 }
 
@@ -30,6 +56,7 @@ impl<M: Arel> Default for Select<M> {
         Self {
             distinct: false,
             sqls: vec![],
+            from: None,
             _marker: PhantomData::<M>,
         }
     }
@@ -45,14 +72,16 @@ impl<M: Arel> Select<M> {
     /// struct User {}
     /// impl Arel for User {}
     /// let select = Select::<User>::new(vec!["name", "age"]);
+    /// #[cfg(not(feature = "mysql"))]
     /// assert_eq!(select.to_sql().unwrap().unwrap().to_sql_string().unwrap(), r#"SELECT "user"."name", "user"."age" FROM "user""#);
     ///
     /// ```
-    pub fn new<T: AsRef<str>>(columns: Vec<T>) -> Self {
+    pub fn new<T: ColumnName<M>>(columns: Vec<T>) -> Self {
         let table_name = M::table_name();
         Self {
             distinct: false,
-            sqls: columns.iter().map(|column| crate::Sql::new(format!(r#""{}"."{}""#, table_name, column.as_ref()))).collect(),
+            sqls: columns.iter().map(|column| crate::Sql::new(quote_column(&table_name, column.column_name()))).collect(),
+            from: None,
             _marker: PhantomData::<M>,
         }
     }
@@ -60,6 +89,7 @@ impl<M: Arel> Select<M> {
         Self {
             distinct: false,
             sqls: vec![sql.into()],
+            from: None,
             _marker: PhantomData::<M>,
         }
     }
@@ -72,6 +102,7 @@ impl<M: Arel> Select<M> {
     /// struct User {}
     /// impl Arel for User {}
     /// let select = Select::<User>::new_sqls(vec!["name", "age"]);
+    /// #[cfg(not(feature = "mysql"))]
     /// assert_eq!(select.to_sql().unwrap().unwrap().to_sql_string().unwrap(), r#"SELECT name, age FROM "user""#);
     ///
     /// ```
@@ -79,8 +110,70 @@ impl<M: Arel> Select<M> {
         Self {
             distinct: false,
             sqls: sqls.into_iter().map(|sql| sql.into()).collect(),
+            from: None,
+            _marker: PhantomData::<M>,
+        }
+    }
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// use arel::statements::Select;
+    /// #[arel]
+    /// struct User {}
+    /// impl Arel for User {}
+    /// let mut select = Select::<User>::default();
+    /// select.append_sql("1 AS one");
+    /// #[cfg(not(feature = "mysql"))]
+    /// assert_eq!(select.to_sql().unwrap().unwrap().to_sql_string().unwrap(), r#"SELECT "user".*, 1 AS one FROM "user""#);
+    ///
+    /// ```
+    pub fn append_sql<S: Into<crate::Sql>>(&mut self, sql: S) -> &mut Self {
+        if self.sqls.is_empty() {
+            self.sqls.push(crate::Sql::new(quote_column(M::table_name(), "*")));
+        }
+        self.sqls.push(sql.into());
+        self
+    }
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// use arel::statements::Select;
+    /// #[arel]
+    /// struct User {}
+    /// impl Arel for User {}
+    /// let mut select = Select::<User>::default();
+    /// select.from_sql(r#""active_user" AS "user""#);
+    /// #[cfg(not(feature = "mysql"))]
+    /// assert_eq!(select.to_sql().unwrap().unwrap().to_sql_string().unwrap(), r#"SELECT "user".* FROM "active_user" AS "user""#);
+    ///
+    /// ```
+    pub fn from_sql<S: Into<crate::Sql>>(&mut self, sql: S) -> &mut Self {
+        self.from = Some(sql.into());
+        self
+    }
+    // replace projection and distinct, keep the FROM source
+    pub(crate) fn replace(&mut self, other: Self) -> &mut Self {
+        self.distinct = other.distinct;
+        self.sqls = other.sqls;
+        self
+    }
+    pub(crate) fn is_distinct(&self) -> bool {
+        self.distinct
+    }
+    pub(crate) fn has_from(&self) -> bool {
+        self.from.is_some()
+    }
+    // render `sqls` as the projection, keep distinct and the FROM source
+    pub(crate) fn to_sql_with_sqls(&self, sqls: Vec<crate::Sql>) -> crate::Sql {
+        Self {
+            distinct: self.distinct,
+            sqls,
+            from: self.from.clone(),
             _marker: PhantomData::<M>,
         }
+        .render()
     }
     pub fn distinct(&mut self) -> &mut Self {
         self.distinct = true;
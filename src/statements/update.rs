@@ -1,4 +1,5 @@
 use crate::prelude::Arel;
+use crate::sql::quote_ident;
 use crate::statements::ArelStatement;
 use std::{fmt::Debug, marker::PhantomData};
 
@@ -14,12 +15,12 @@ pub struct Update<M: Arel> {
 impl<M: Arel> ArelStatement for Update<M> {
     fn to_sql(&self) -> crate::Result<Option<crate::Sql>> {
         let table_name = M::table_name();
-        let mut final_sql = crate::Sql::new(format!(r#"UPDATE "{}" SET "#, table_name));
+        let mut final_sql = crate::Sql::new(format!("UPDATE {} SET ", quote_ident(&table_name)));
 
         let len = self.fields.len();
         for (idx, field) in self.fields.iter().enumerate() {
             let value = &self.values[idx];
-            final_sql.push_str_with_bind(format!(r#""{}" = "#, field), value.clone());
+            final_sql.push_str_with_bind(format!("{} = ", quote_ident(field)), value.clone());
             if idx < len - 1 {
                 final_sql.push_str(", ");
             }
@@ -29,12 +30,14 @@ impl<M: Arel> ArelStatement for Update<M> {
         let len = self.where_fields.len();
         for (idx, field) in self.where_fields.iter().enumerate() {
             let value = &self.where_values[idx];
-            final_sql.push_str_with_bind(format!(r#""{}" = "#, field), value.clone());
+            final_sql.push_str_with_bind(format!("{} = ", quote_ident(field)), value.clone());
             if idx < len - 1 {
                 final_sql.push_str(" AND ");
             }
         }
-        final_sql.push_str(" RETURNING *");
+        if crate::db::dialect::compiled().supports_returning() {
+            final_sql.push_str(" RETURNING *");
+        }
         Ok(Some(final_sql))
     }
 }
@@ -7,6 +7,12 @@ use std::ops::{Deref, DerefMut};
 #[derive(Debug)]
 pub struct Where<M: Arel>(Filter<M>);
 
+impl<M: Arel> Clone for Where<M> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
 impl<M: Arel> ArelStatement for Where<M> {
     fn to_sql(&self) -> crate::Result<Option<crate::Sql>> {
         if let Some(filter_sql) = self.0.to_sql()? {
@@ -19,6 +25,12 @@ impl<M: Arel> ArelStatement for Where<M> {
     }
 }
 
+impl<M: Arel> Where<M> {
+    pub fn into_inner(self) -> Filter<M> {
+        self.0
+    }
+}
+
 impl<M: Arel> Deref for Where<M> {
     type Target = Filter<M>;
     fn deref(&self) -> &Self::Target {
@@ -41,7 +53,7 @@ impl<M: Arel> DerefMut for Where<M> {
 /// impl Arel for User {}
 /// let mut r#where = Where::<User>::default();
 /// r#where.and_filter("username", "sanmu");
-/// #[cfg(any(feature = "sqlite", feature = "mysql"))]
+/// #[cfg(feature = "sqlite")]
 /// assert_eq!(r#where.to_sql().unwrap().unwrap().to_sql_string().unwrap(), r#"WHERE "user"."username" = ?"#);
 /// ```
 impl<M: Arel> Default for Where<M> {
@@ -0,0 +1,60 @@
+use crate::sql::quote_ident;
+use crate::statements::ArelStatement;
+
+#[derive(Debug, Clone, Default)]
+pub struct With {
+    recursive: bool,
+    sqls: Vec<crate::Sql>,
+}
+
+impl ArelStatement for With {
+    fn to_sql(&self) -> crate::Result<Option<crate::Sql>> {
+        if !self.sqls.is_empty() {
+            let mut final_sql = crate::Sql::new(if self.recursive { "WITH RECURSIVE " } else { "WITH " });
+            final_sql.push_sqls(self.sqls.clone(), ", ");
+            Ok(Some(final_sql))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl With {
+    pub(crate) fn len(&self) -> usize {
+        self.sqls.len()
+    }
+    pub(crate) fn remove_leading(&mut self, count: usize) {
+        self.sqls.drain(..count.min(self.sqls.len()));
+    }
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// use arel::statements::With;
+    /// let mut with = With::default();
+    /// with.append("recent", "SELECT * FROM \"user\" WHERE \"user\".\"id\" > 10");
+    /// #[cfg(not(feature = "mysql"))]
+    /// assert_eq!(with.to_sql().unwrap().unwrap().to_sql_string().unwrap(), r#"WITH "recent" AS (SELECT * FROM "user" WHERE "user"."id" > 10)"#);
+    ///
+    /// with.append_recursive("tree", "SELECT 1 AS n", "SELECT n + 1 FROM \"tree\" WHERE n < 5");
+    /// #[cfg(not(feature = "mysql"))]
+    /// assert_eq!(
+    ///     with.to_sql().unwrap().unwrap().to_sql_string().unwrap(),
+    ///     r#"WITH RECURSIVE "recent" AS (SELECT * FROM "user" WHERE "user"."id" > 10), "tree" AS (SELECT 1 AS n UNION ALL SELECT n + 1 FROM "tree" WHERE n < 5)"#
+    /// );
+    ///
+    /// ```
+    pub fn append<N: AsRef<str>, S: Into<crate::Sql>>(&mut self, name: N, sql: S) -> &mut Self {
+        let mut with_sql = crate::Sql::new(format!("{} AS (", quote_ident(name)));
+        with_sql.push_sql(sql.into()).push_str(")");
+        self.sqls.push(with_sql);
+        self
+    }
+    pub fn append_recursive<N: AsRef<str>, A: Into<crate::Sql>, R: Into<crate::Sql>>(&mut self, name: N, anchor: A, recursive_part: R) -> &mut Self {
+        let mut with_sql = crate::Sql::new(format!("{} AS (", quote_ident(name)));
+        with_sql.push_sql(anchor.into()).push_str(" UNION ALL ").push_sql(recursive_part.into()).push_str(")");
+        self.recursive = true;
+        self.sqls.push(with_sql);
+        self
+    }
+}
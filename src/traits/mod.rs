@@ -23,17 +23,22 @@ pub trait SuperArel {
         Ok(crate::db::get_pool()?)
     }
     fn primary_values(&self) -> Vec<crate::Value>;
+    /// The current value of a column, `None` when it is not set or no such column.
+    fn attribute_value(&self, name: &str) -> Option<crate::Value>;
+    /// The columns set with a new value, in field order.
+    fn changed_values(&self) -> Vec<(&'static str, crate::Value)>;
     fn assign(&mut self, other: &Self) -> &mut Self;
     fn is_dirty(&self) -> bool;
+    /// `executor` is any executor, on mysql a pool, connection or transaction, see [`crate::db::WriteExecutor`].
     async fn insert_with_exec<'a, E>(&mut self, executor: E) -> crate::Result<()>
     where
-        E: sqlx::Executor<'a, Database = crate::db::Database>;
+        E: crate::db::WriteExecutor<'a>;
     async fn update_with_exec<'a, E>(&mut self, executor: E) -> crate::Result<()>
     where
-        E: sqlx::Executor<'a, Database = crate::db::Database>;
+        E: crate::db::WriteExecutor<'a>;
     async fn increment_with_exec<'a, K: Send + ToString, E>(&mut self, key: K, step: i32, executor: E) -> crate::Result<()>
     where
-        E: sqlx::Executor<'a, Database = crate::db::Database>;
+        E: crate::db::WriteExecutor<'a>;
     async fn destroy_with_exec<'a, E>(&mut self, executor: E) -> crate::Result<()>
     where
         E: sqlx::Executor<'a, Database = crate::db::Database>;
@@ -47,11 +52,37 @@ pub trait Arel: SuperArel + ArelPersisted + Send {
     fn pool() -> crate::Result<&'static sqlx::Pool<crate::db::Database>> {
         Self::_pool()
     }
+    /// Applied by [`Arel::query`] to every query, remove it with [`crate::SelectManager::unscoped`] or [`crate::SelectManager::unscope_where`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// #[arel]
+    /// struct User {}
+    /// impl Arel for User {
+    ///     fn default_scope(query: &mut arel::SelectManager<Self>) {
+    ///         query.r#where("deleted_at", None::<String>);
+    ///     }
+    /// }
+    /// #[cfg(not(feature = "mysql"))]
+    /// assert_eq!(User::query().to_sql().unwrap().to_sql_string().unwrap(), r#"SELECT "user".* FROM "user" WHERE "user"."deleted_at" IS NULL"#);
+    /// #[cfg(not(feature = "mysql"))]
+    /// assert_eq!(User::query().unscope_where("deleted_at").to_sql().unwrap().to_sql_string().unwrap(), r#"SELECT "user".* FROM "user""#);
+    /// ```
+    fn default_scope(_query: &mut crate::manager::SelectManager<Self>)
+    where
+        Self: Sized,
+    {
+    }
     fn query() -> crate::manager::SelectManager<Self>
     where
         Self: Sized,
     {
-        crate::manager::SelectManager::<Self>::default()
+        let mut query = crate::manager::SelectManager::<Self>::default();
+        Self::default_scope(&mut query);
+        query.mark_default_scope();
+        query
     }
     async fn with_transaction<'a, F: Send>(callback: F) -> crate::Result<Option<Self>>
     where
@@ -127,9 +158,94 @@ pub trait Arel: SuperArel + ArelPersisted + Send {
         self.after_save_commit().await?;
         Ok(())
     }
+    /// Insert `models` with multi-row `INSERT` statements, see [`crate::statements::insert::Insert::batches`].
+    ///
+    /// Validations and callbacks are skipped. Returns the inserted rows read back with `RETURNING *`,
+    /// mysql has no `RETURNING` and returns an empty `Vec`.
+    async fn insert_all_with_tx(models: Vec<Self>, tx: &mut sqlx::Transaction<'_, crate::db::Database>) -> crate::Result<Vec<Self>>
+    where
+        Self: Sized,
+        for<'b> Self: Unpin + sqlx::FromRow<'b, crate::db::DatabaseRow>,
+    {
+        insert_batches_with_tx(models.iter().map(|model| model.changed_values()).collect(), None, tx).await
+    }
+    /// [`Arel::insert_all_with_tx`] in one transaction.
+    async fn insert_all(models: Vec<Self>) -> crate::Result<Vec<Self>>
+    where
+        Self: Sized,
+        for<'b> Self: Unpin + sqlx::FromRow<'b, crate::db::DatabaseRow>,
+    {
+        let mut tx = Self::pool()?.begin().await?;
+        match Self::insert_all_with_tx(models, &mut tx).await {
+            Ok(models) => {
+                tx.commit().await?;
+                Ok(models)
+            }
+            Err(e) => {
+                tx.rollback().await?;
+                Err(e)
+            }
+        }
+    }
+    /// Insert the changed columns of `self`, or resolve the conflict by `on_conflict`, skipping validations and callbacks.
+    ///
+    /// `self` is reloaded from the written row and `true` returned when a row was written, `false` when
+    /// `DO NOTHING` skipped it. Mysql reads the row back like [`SuperArel::insert_with_exec`], by the written
+    /// conflict columns when the primary key is not set, and counts a row `DO NOTHING` kept as written.
+    async fn upsert_with_exec<'a, E>(&mut self, on_conflict: crate::statements::OnConflict, executor: E) -> crate::Result<bool>
+    where
+        Self: Sized,
+        for<'b> Self: Unpin + sqlx::FromRow<'b, crate::db::DatabaseRow>,
+        E: crate::db::WriteExecutor<'a>,
+    {
+        let changed_values = self.changed_values();
+        let unique_keys = on_conflict.target_columns();
+        let insert = crate::statements::Insert::<Self>::new(changed_values.iter().map(|(field, _)| *field).collect(), changed_values.iter().map(|(_, value)| value.clone()).collect()).on_conflict(on_conflict);
+        let insert_sql = crate::statements::ArelStatement::to_sql(&insert)?.ok_or_else(|| crate::Error::Message("sql error".to_string()))?;
+        match insert_sql.fetch_written_optional_as_with_exec(changed_values, vec![], &unique_keys, executor).await? {
+            Some(model) => {
+                *self = model;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+    async fn upsert(&mut self, on_conflict: crate::statements::OnConflict) -> crate::Result<bool>
+    where
+        Self: Sized,
+        for<'b> Self: Unpin + sqlx::FromRow<'b, crate::db::DatabaseRow>,
+    {
+        self.upsert_with_exec(on_conflict, Self::pool()?).await
+    }
+    /// [`Arel::insert_all_with_tx`] resolving conflicts by `on_conflict`, rows skipped by `DO NOTHING` are not returned.
+    async fn upsert_all_with_tx(models: Vec<Self>, on_conflict: crate::statements::OnConflict, tx: &mut sqlx::Transaction<'_, crate::db::Database>) -> crate::Result<Vec<Self>>
+    where
+        Self: Sized,
+        for<'b> Self: Unpin + sqlx::FromRow<'b, crate::db::DatabaseRow>,
+    {
+        insert_batches_with_tx(models.iter().map(|model| model.changed_values()).collect(), Some(&on_conflict), tx).await
+    }
+    /// [`Arel::upsert_all_with_tx`] in one transaction.
+    async fn upsert_all(models: Vec<Self>, on_conflict: crate::statements::OnConflict) -> crate::Result<Vec<Self>>
+    where
+        Self: Sized,
+        for<'b> Self: Unpin + sqlx::FromRow<'b, crate::db::DatabaseRow>,
+    {
+        let mut tx = Self::pool()?.begin().await?;
+        match Self::upsert_all_with_tx(models, on_conflict, &mut tx).await {
+            Ok(models) => {
+                tx.commit().await?;
+                Ok(models)
+            }
+            Err(e) => {
+                tx.rollback().await?;
+                Err(e)
+            }
+        }
+    }
     async fn decrement_with_exec<'a, K: Send + ToString, E>(&mut self, key: K, step: i32, executor: E) -> crate::Result<()>
     where
-        E: sqlx::Executor<'a, Database = crate::db::Database>,
+        E: crate::db::WriteExecutor<'a>,
     {
         self.increment_with_exec(key, step * -1, executor).await
     }
@@ -169,3 +285,22 @@ pub trait Arel: SuperArel + ArelPersisted + Send {
         Ok(())
     }
 }
+
+// the multi-row inserts behind insert_all and upsert_all, `rows` are the changed values of each model
+async fn insert_batches_with_tx<M>(rows: Vec<Vec<(&'static str, crate::Value)>>, on_conflict: Option<&crate::statements::OnConflict>, tx: &mut sqlx::Transaction<'_, crate::db::Database>) -> crate::Result<Vec<M>>
+where
+    for<'b> M: Arel + Unpin + sqlx::FromRow<'b, crate::db::DatabaseRow>,
+{
+    let mut inserted = vec![];
+    for insert in crate::statements::insert::Insert::<M>::batches(rows, on_conflict) {
+        if let Some(insert_sql) = crate::statements::ArelStatement::to_sql(&insert)? {
+            if cfg!(feature = "mysql") {
+                insert_sql.exec(tx.as_mut()).await?;
+            } else {
+                let models: Vec<M> = insert_sql.fetch_all_as_with_exec(tx.as_mut()).await?;
+                inserted.extend(models);
+            }
+        }
+    }
+    Ok(inserted)
+}
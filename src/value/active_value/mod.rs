@@ -145,6 +145,21 @@ where
             _ => Err(crate::Error::Message("value not set".into())),
         }
     }
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arel::prelude::*;
+    /// use arel::{Value, ActiveValue};
+    /// let not_set: ActiveValue<i32> = arel::ActiveValue::NotSet;
+    /// assert!(not_set.value().is_none());
+    ///
+    /// let changed = arel::ActiveValue::Changed(1, Box::new(ActiveValue::NotSet));
+    /// assert_eq!(changed.value(), Some(&1));
+    /// ```
+    pub fn value(&self) -> Option<&V> {
+        self.get_value().ok()
+    }
 }
 
 #[allow(non_snake_case)]
@@ -85,6 +85,36 @@ pub struct User {
 }
 impl Arel for User {}
 
+// the first half of the users, hidden behind a default scope
+#[arel(table_name = "users")]
+pub struct EarlyUser {
+    #[arel(primary_key)]
+    id: i32,
+    name: String,
+}
+impl Arel for EarlyUser {
+    fn default_scope(query: &mut arel::manager::SelectManager<Self>) {
+        query.where_lte("id", 50);
+    }
+}
+
+#[arel]
+impl User {
+    #[arel(scope)]
+    fn admins(q: &mut arel::manager::SelectManager<Self>) {
+        q.r#where("type", Type::Admin);
+    }
+    /// `id <= max_id`, newest first
+    #[arel(scope)]
+    pub fn recent(q: &mut arel::manager::SelectManager<Self>, max_id: i32) -> &mut arel::manager::SelectManager<Self> {
+        q.where_lte("id", max_id).order_desc("id")
+    }
+    #[arel(scope)]
+    fn named_like<S: AsRef<str>>(q: &mut arel::manager::SelectManager<Self>, prefix: S) {
+        q.where_starts_with(User::columns().name, prefix);
+    }
+}
+
 // impl<'r> arel::sqlx::FromRow<'r, arel::db::DatabaseRow> for User {
 //     fn from_row(row: &'r arel::db::DatabaseRow) -> Result<Self, sqlx::Error> {
 //         let mut model = Self::default();
@@ -141,9 +171,28 @@ mod tests {
         init_db().await?;
 
         test_query().await?;
+        test_predicates().await?;
+        test_with().await?;
+        test_compound().await?;
+        test_select_expr().await?;
+        test_aggregate().await?;
+        test_group_aggregate().await?;
+        test_keyset_page().await?;
+        test_fetch_page().await?;
+        test_fetch_stream().await?;
+        test_find_in_batches().await?;
+        test_lock().await?;
+        test_order().await?;
+        test_scope().await?;
+        test_default_scope().await?;
+        test_clone_merge().await?;
+        test_update_all().await?;
         test_insert().await?;
         test_update().await?;
         test_destroy().await?;
+        test_delete_all().await?;
+        test_insert_all().await?;
+        test_upsert().await?;
 
         Ok(())
     }
@@ -160,6 +209,365 @@ mod tests {
         let user = User::query().r#where("id", 99999).fetch_one_optional().await?;
         assert!(user.is_none());
 
+        let user = User::query().r#where(User::columns().id, 1).order_desc(User::columns().id).fetch_one().await?;
+        assert_eq!(user.id, arel::ActiveValue::Unchanged(1.into()));
+
+        Ok(())
+    }
+    async fn test_predicates() -> anyhow::Result<()> {
+        let users = User::query().where_gt("id", 10).where_lte(User::columns().id, 20).fetch_all().await?;
+        assert_eq!(users.len(), 10);
+
+        let users = User::query().where_between("id", 1, 5).where_null_safe_eq("age", Option::<i32>::None).fetch_all().await?;
+        assert_eq!(users.len(), 5);
+
+        let users = User::query().where_starts_with("name", "name-10").fetch_all().await?;
+        assert_eq!(users.len(), 2);
+        let users = User::query().where_ilike("name", "NAME-9_").fetch_all().await?;
+        assert_eq!(users.len(), 10);
+        let users = User::query().where_contains("name", "e-9_").fetch_all().await?;
+        assert_eq!(users.len(), 0);
+
+        let users = User::query().where_in_query("id", User::query().select(vec!["id"]).where_lte("id", 3)).fetch_all().await?;
+        assert_eq!(users.len(), 3);
+        let count = User::query().select_sql("COUNT(*)").where_exists(User::query().select_sql("1").where_gt("id", 1000)).fetch_count().await?;
+        assert_eq!(count, 0);
+        let count = User::query().select_sql("COUNT(*)").where_not_exists(User::query().select_sql("1").where_gt("id", 1000)).fetch_count().await?;
+        assert_eq!(count, 100);
+
+        Ok(())
+    }
+    async fn test_with() -> anyhow::Result<()> {
+        let users = User::query()
+            .with("young", User::query().where_lte("id", 10))
+            .from_with("young")
+            .where_gt("id", 5)
+            .fetch_all()
+            .await?;
+        assert_eq!(users.len(), 5);
+
+        let count = User::query()
+            .select_sql("COUNT(*)")
+            .with("recent", User::query().select(vec!["id"]).where_gt("id", 90))
+            .join_with(arel::JoinConst::InnerJoin, "recent", r#""recent"."id" = "users"."id""#)
+            .fetch_count()
+            .await?;
+        assert_eq!(count, 10);
+
+        let users = User::query()
+            .with_recursive(
+                "tree",
+                User::query().r#where("id", 1),
+                User::query().join_sql(r#"INNER JOIN "tree" ON "users"."id" = "tree"."id" + 1"#).where_sql(r#""tree"."id" < 5"#),
+            )
+            .from_with("tree")
+            .order_asc("id")
+            .fetch_all()
+            .await?;
+        assert_eq!(users.iter().map(|u| u.id.value().cloned()).collect::<Vec<_>>(), (1..=5).map(|id| Some(id.into())).collect::<Vec<_>>());
+
+        Ok(())
+    }
+    async fn test_compound() -> anyhow::Result<()> {
+        let users = User::query().where_lte("id", 3).union(User::query().where_gt("id", 98)).order_desc("id").fetch_all().await?;
+        assert_eq!(users.len(), 5);
+        assert_eq!(users[0].id.value().cloned(), Some(100.into()));
+
+        let users = User::query().where_lte("id", 3).union_all(User::query().where_lte("id", 2)).fetch_all().await?;
+        assert_eq!(users.len(), 5);
+        let users = User::query().where_lte("id", 3).intersect(User::query().where_gte("id", 2)).fetch_all().await?;
+        assert_eq!(users.len(), 2);
+        let users = User::query().where_lte("id", 10).except(User::query().where_gt("id", 5)).order_asc("id").limit(2).offset(1).fetch_all().await?;
+        assert_eq!(users.iter().map(|u| u.id.value().cloned()).collect::<Vec<_>>(), vec![Some(2.into()), Some(3.into())]);
+
+        Ok(())
+    }
+    async fn test_select_expr() -> anyhow::Result<()> {
+        use arel::statements::expr::{Expr, Window};
+        let columns = User::columns();
+        let rows = User::query()
+            .select_expr(vec![
+                Expr::from(columns.id),
+                Expr::row_number().over(Window::new().order_by(columns.id, arel::SortConst::Desc)).alias("rn"),
+                (Expr::from(columns.id) * 10 + 1).alias("calc"),
+                Expr::case().when(Expr::from(columns.id).lt(2), Expr::value("first")).otherwise(Expr::value("rest")).alias("stage"),
+            ])
+            .where_lte("id", 3)
+            .order_asc("id")
+            .fetch_all_as::<(i32, i64, i64, String)>()
+            .await?;
+        assert_eq!(rows, vec![(1, 3, 11, "first".to_string()), (2, 2, 21, "rest".to_string()), (3, 1, 31, "rest".to_string())]);
+
+        let row = User::query()
+            .select_expr(vec![Expr::count_all(), Expr::count_distinct(columns.name), Expr::max(columns.id), Expr::coalesce(vec![Expr::sum(columns.age), Expr::value(0)])])
+            .fetch_one_as::<(i64, i64, i32, i64)>()
+            .await?;
+        assert_eq!(row, (100, 100, 100, 0));
+
+        // string arguments are columns, not bound values
+        let sum: (i64,) = User::query().select_expr(vec![Expr::sum("id")]).where_lte("id", 3).fetch_one_as().await?;
+        assert_eq!(sum, (6,));
+        let rows = User::query()
+            .select_expr(vec![Expr::row_number().over(Window::new().partition_by("name").order_by("id", arel::SortConst::Asc))])
+            .where_lte("id", 3)
+            .fetch_all_as::<(i64,)>()
+            .await?;
+        assert_eq!(rows, vec![(1,), (1,), (1,)]);
+
+        Ok(())
+    }
+    async fn test_aggregate() -> anyhow::Result<()> {
+        let mut query = User::query();
+        query.where_lte("id", 10).order_desc("id");
+        assert_eq!(query.count().await?, 10);
+        assert_eq!(query.sum::<i64, _>("id").await?, Some(55));
+        assert_eq!(query.max::<i32, _>(User::columns().id).await?, Some(10));
+        assert_eq!(query.min::<i32, _>("id").await?, Some(1));
+        assert_eq!(query.avg::<f64, _>("id").await?, Some(5.5));
+        assert_eq!(query.sum::<i64, _>("age").await?, None);
+        assert_eq!(query.count_distinct("type").await?, 1);
+        assert_eq!(query.pluck::<String, _>("name").await?[0], "name-10");
+        assert_eq!(query.pick::<String, _>("name").await?, Some("name-10".to_string()));
+        assert_eq!(query.ids::<i32>().await?.len(), 10);
+        assert!(query.exists().await?);
+        assert!(!User::query().where_gt("id", 1000).exists().await?);
+        // an offset alone still runs, sqlite takes no OFFSET without a LIMIT
+        assert!(User::query().offset(99).exists().await?);
+        assert!(!User::query().offset(100).exists().await?);
+        assert_eq!(User::query().offset(98).fetch_all().await?.len(), 2);
+        // the caller's projection is untouched
+        assert_eq!(query.fetch_all().await?.len(), 10);
+
+        let mut limited = User::query();
+        limited.limit(3).offset(2).order_asc("id");
+        assert_eq!(limited.count().await?, 3);
+        assert_eq!(limited.sum::<i64, _>("id").await?, Some(3 + 4 + 5));
+        assert_eq!(limited.pick::<i32, _>("id").await?, Some(3));
+        assert_eq!(User::query().group(vec!["type"]).count().await?, 1);
+
+        Ok(())
+    }
+    async fn test_group_aggregate() -> anyhow::Result<()> {
+        let counts = User::query().group(vec!["type"]).group_count::<String>().await?;
+        assert_eq!(counts, std::collections::HashMap::from([("ADMIN".to_string(), 100)]));
+        let sums = User::query().where_lte("id", 10).group(vec!["type"]).group_sum::<String, i64, _>("id").await?;
+        assert_eq!(sums.get("ADMIN"), Some(&55));
+
+        let counts = User::query().group(vec!["type", "age"]).group_count_vec::<(String, Option<i32>)>().await?;
+        assert_eq!(counts, vec![(("ADMIN".to_string(), None), 100)]);
+        let sums = User::query().where_lte("id", 3).group(vec!["name", "type"]).order_desc("name").group_sum_vec::<(String, String), i64, _>("id").await?;
+        assert_eq!(sums[0], (("name-3".to_string(), "ADMIN".to_string()), 3));
+        assert_eq!(sums.len(), 3);
+
+        assert!(User::query().group_count::<String>().await.is_err());
+        assert!(User::query().group(vec!["type", "age"]).group_count::<String>().await.is_err());
+
+        Ok(())
+    }
+    async fn test_keyset_page() -> anyhow::Result<()> {
+        let ids = |page: &arel::manager::KeysetPage<User>| page.items.iter().map(|u| u.id.value().cloned()).collect::<Vec<_>>();
+        let mut query = User::query();
+        query.where_lte("id", 10).order_desc("id");
+
+        let first = query.fetch_keyset_page(4, None).await?;
+        assert_eq!(ids(&first), [10, 9, 8, 7].map(|id| Some(id.into())));
+        assert!(first.prev_cursor.is_none());
+        // cursors travel as opaque tokens
+        let token = serde_json::to_string(&first.next_cursor)?;
+        let next_cursor: Option<arel::manager::Cursor> = serde_json::from_str(&token)?;
+
+        let second = query.fetch_keyset_page(4, next_cursor.as_ref()).await?;
+        assert_eq!(ids(&second), [6, 5, 4, 3].map(|id| Some(id.into())));
+        let last = query.fetch_keyset_page(4, second.next_cursor.as_ref()).await?;
+        assert_eq!(ids(&last), [2, 1].map(|id| Some(id.into())));
+        assert!(last.next_cursor.is_none());
+
+        let back = query.fetch_keyset_page(4, last.prev_cursor.as_ref()).await?;
+        assert_eq!(ids(&back), ids(&second));
+        let back = query.fetch_keyset_page(4, back.prev_cursor.as_ref()).await?;
+        assert_eq!(ids(&back), ids(&first));
+        assert!(back.prev_cursor.is_none());
+        assert!(back.next_cursor.is_some());
+
+        // mixed directions use the expanded form
+        let mut query = User::query();
+        query.where_lte("id", 5).order_asc("type").order_desc("id");
+        let first = query.fetch_keyset_page(3, None).await?;
+        let second = query.fetch_keyset_page(3, first.next_cursor.as_ref()).await?;
+        assert_eq!(ids(&second), [2, 1].map(|id| Some(id.into())));
+
+        Ok(())
+    }
+    async fn test_fetch_page() -> anyhow::Result<()> {
+        let mut query = User::query();
+        query.where_lte("id", 25).order_desc("id").limit(3);
+        let page = query.fetch_page(3, 10).await?;
+        assert_eq!((page.total, page.total_pages, page.page, page.has_next), (25, 3, 3, false));
+        assert_eq!(page.items.iter().map(|u| u.id.value().cloned()).collect::<Vec<_>>(), (1..=5).rev().map(|id| Some(id.into())).collect::<Vec<_>>());
+        let json = serde_json::to_value(&page)?;
+        assert_eq!(json["total_pages"], 3);
+
+        let page = User::query().group(vec!["type"]).fetch_page(1, 10).await?;
+        assert_eq!((page.total, page.items.len(), page.has_next), (1, 1, false));
+
+        let page = User::with_transaction(|tx| {
+            Box::pin(async move {
+                let page = User::query().fetch_page_with_tx(2, 40, tx).await?;
+                assert_eq!((page.total, page.items.len(), page.has_next), (100, 40, true));
+                std::result::Result::Ok(None)
+            })
+        })
+        .await?;
+        assert!(page.is_none());
+
+        let mut conn = User::pool()?.acquire().await?;
+        let page = User::query().where_lte("id", 15).fetch_page_with_acquire(2, 10, &mut *conn).await?;
+        assert_eq!((page.total, page.items.len(), page.has_next), (15, 5, false));
+
+        Ok(())
+    }
+    async fn test_fetch_stream() -> anyhow::Result<()> {
+        use futures_util::TryStreamExt;
+        let mut query = User::query();
+        query.where_gt("id", 90).order_asc("id");
+        let mut stream = query.fetch_stream();
+        let mut ids = vec![];
+        while let Some(user) = stream.try_next().await? {
+            assert!(user.persited());
+            ids.push(user.id.value().cloned());
+        }
+        assert_eq!(ids, (91..=100).map(|id| Some(id.into())).collect::<Vec<_>>());
+
+        let users: Vec<User> = User::query().where_lte("id", 3).fetch_stream().try_collect().await?;
+        assert_eq!(users.len(), 3);
+
+        let mut stream = User::query().where_sql("no_such_column = 1").fetch_stream();
+        assert!(stream.try_next().await.is_err());
+        Ok(())
+    }
+    async fn test_find_in_batches() -> anyhow::Result<()> {
+        let mut sizes = vec![];
+        let mut ids = vec![];
+        let mut query = User::query();
+        query.where_gt("id", 75).order_desc("id").limit(3);
+        query
+            .find_in_batches(10, |batch| {
+                sizes.push(batch.len());
+                ids.extend(batch.iter().map(|u| u.id.value().cloned()));
+                async { std::result::Result::Ok(()) }
+            })
+            .await?;
+        assert_eq!(sizes, vec![10, 10, 5]);
+        assert_eq!(ids, (76..=100).map(|id| Some(id.into())).collect::<Vec<_>>());
+
+        let mut count = 0;
+        User::query()
+            .where_lte("id", 20)
+            .find_each(5, |user| {
+                count += 1;
+                assert!(user.persited());
+                async { std::result::Result::Ok(()) }
+            })
+            .await?;
+        assert_eq!(count, 20);
+
+        let ret = User::query()
+            .find_each(5, |_| async { Err(arel::Error::Message("stop".to_string())) })
+            .await;
+        assert!(ret.is_err());
+        Ok(())
+    }
+    async fn test_lock() -> anyhow::Result<()> {
+        let err = User::query().r#where("id", 1).lock().fetch_one().await.unwrap_err();
+        assert!(err.to_string().contains("sqlite does not support row locking"));
+        let err = User::query().lock_skip_locked().fetch_all().await.unwrap_err();
+        assert!(err.to_string().contains("FOR UPDATE"));
+        // locks are dropped from count queries
+        assert_eq!(User::query().lock().count().await?, 100);
+        Ok(())
+    }
+    async fn test_order() -> anyhow::Result<()> {
+        let mut query = User::query();
+        query.where_lte("id", 3).order_expr(arel::Expr::case().when(arel::Expr::from(User::columns().id).eq(2), 0).otherwise(1), arel::SortConst::Asc).order_asc("id");
+        let ids: Vec<i64> = query.pluck("id").await?;
+        assert_eq!(ids, vec![2, 1, 3]);
+        query.reverse_order();
+        let ids: Vec<i64> = query.pluck("id").await?;
+        assert_eq!(ids, vec![3, 1, 2]);
+        query.reorder("id", arel::SortConst::Desc);
+        let ids: Vec<i64> = query.pluck("id").await?;
+        assert_eq!(ids, vec![3, 2, 1]);
+
+        let mut query = User::query();
+        query.where_lte("id", 3).order_nulls("age", arel::SortConst::Asc, arel::NullsConst::Last).order_desc("id");
+        let ids: Vec<i64> = query.pluck("id").await?;
+        assert_eq!(ids, vec![3, 2, 1]);
+        query.unorder().order_asc("id");
+        let ids: Vec<i64> = query.pluck("id").await?;
+        assert_eq!(ids, vec![1, 2, 3]);
+        Ok(())
+    }
+    async fn test_scope() -> anyhow::Result<()> {
+        let ids: Vec<i64> = User::query().admins().recent(5).named_like("name-").pluck("id").await?;
+        assert_eq!(ids, vec![5, 4, 3, 2, 1]);
+        let mut query = User::query();
+        query.recent(20).named_like("name-1");
+        assert_eq!(query.count().await?, 11);
+        Ok(())
+    }
+    async fn test_default_scope() -> anyhow::Result<()> {
+        assert_eq!(EarlyUser::query().count().await?, 50);
+        assert_eq!(EarlyUser::query().where_gt("id", 40).count().await?, 10);
+        assert_eq!(EarlyUser::query().unscope_where("id").count().await?, 100);
+        assert_eq!(EarlyUser::query().unscoped().where_gt("id", 40).count().await?, 60);
+        // clauses chained before unscoped() stay
+        assert_eq!(EarlyUser::query().where_gt("id", 40).unscoped().count().await?, 60);
+        let ids = EarlyUser::query().where_gt("id", 40).order_desc("id").limit(2).unscoped().ids::<i32>().await?;
+        assert_eq!(ids, vec![100, 99]);
+        assert_eq!(EarlyUser::query().where_ilike("name", "NAME-1%").unscope_where("name").count().await?, 50);
+        Ok(())
+    }
+    async fn test_clone_merge() -> anyhow::Result<()> {
+        let mut base = User::query();
+        base.where_lte("id", 30);
+        let mut data = base.clone();
+        data.order_desc("id").limit(2);
+        assert_eq!(base.count().await?, 30);
+        let ids: Vec<i64> = data.pluck("id").await?;
+        assert_eq!(ids, vec![30, 29]);
+
+        let mut odd_tens = User::query();
+        odd_tens.where_gt("id", 10).where_or("id", 1).order_asc("id");
+        let mut query = base.clone();
+        query.merge(&odd_tens).limit(3);
+        let ids: Vec<i64> = query.pluck("id").await?;
+        assert_eq!(ids, vec![1, 11, 12]);
+
+        let mut query = User::query();
+        query.where_lte("id", 2).or(User::query().where_gt("id", 98)).order_asc("id");
+        let ids: Vec<i64> = query.pluck("id").await?;
+        assert_eq!(ids, vec![1, 2, 99, 100]);
+        Ok(())
+    }
+    async fn test_update_all() -> anyhow::Result<()> {
+        let mut query = User::query();
+        query.order_desc("id").limit(3);
+        let rows_affected = query.update_all(vec![("age", 10)]).await?;
+        assert_eq!(rows_affected, 3);
+        let ages: Vec<Option<i32>> = User::query().where_gte("id", 98).order_asc("id").pluck("age").await?;
+        assert_eq!(ages, vec![Some(10), Some(10), Some(10)]);
+
+        let users = User::query()
+            .where_gte("id", 99)
+            .update_all_returning(vec![("age", arel::Expr::from(User::columns().age) + 5)])
+            .await?;
+        assert_eq!(users.len(), 2);
+        let ages: Vec<Option<i32>> = User::query().where_gte("id", 98).order_asc("id").pluck("age").await?;
+        assert_eq!(ages, vec![Some(10), Some(15), Some(15)]);
+
+        let rows_affected = User::query().where_gte("id", 98).update_manager().set_sql(r#""age" = NULL"#).execute().await?;
+        assert_eq!(rows_affected, 3);
+        assert_eq!(User::query().where_sql(r#""age" IS NOT NULL"#).count().await?, 0);
         Ok(())
     }
     async fn test_insert() -> anyhow::Result<()> {
@@ -173,6 +581,17 @@ mod tests {
         assert!(new_user.persited());
         assert_eq!(new_user.name.value().unwrap(), "hello");
         assert!(new_user.id.value().is_some());
+
+        // pools, connections and transactions all take the *_with_exec methods
+        let mut user = User { name: Set("hello-pool"), ..Default::default() };
+        user.insert_with_exec(arel::db::get_pool()?).await?;
+        assert!(user.persited());
+        let mut tx = arel::db::get_pool()?.begin().await?;
+        user.name.set("hello-tx");
+        user.update_with_exec(&mut *tx).await?;
+        tx.commit().await?;
+        assert_eq!(user.name, arel::ActiveValue::Unchanged("hello-tx".into()));
+        user.destroy().await?;
         Ok(())
     }
     async fn test_update() -> anyhow::Result<()> {
@@ -201,4 +620,58 @@ mod tests {
 
         Ok(())
     }
+    async fn test_delete_all() -> anyhow::Result<()> {
+        let users = User::query().where_between("id", 95, 96).destroy_all().await?;
+        assert_eq!(users.len(), 2);
+        assert!(users.iter().all(|user| !user.persited()));
+
+        let mut query = User::query();
+        query.where_lte("id", 94).order_desc("id").limit(2);
+        assert_eq!(query.delete_all().await?, 2);
+        let ids: Vec<i32> = User::query().where_gte("id", 90).order_asc("id").pluck("id").await?;
+        assert_eq!(ids, vec![90, 91, 92, 97, 98, 99, 100]);
+
+        assert_eq!(User::query().where_gte("id", 99).delete_all().await?, 2);
+        Ok(())
+    }
+    async fn test_insert_all() -> anyhow::Result<()> {
+        let models = vec![
+            User { name: Set("bulk-1"), ..Default::default() },
+            User { name: Set("bulk-2"), ..Default::default() },
+            User { name: Set("bulk-3"), age: Set(30), ..Default::default() },
+        ];
+        let users = User::insert_all(models).await?;
+        assert_eq!(users.len(), 3);
+        assert!(users.iter().all(|user| user.persited() && user.id.value().is_some()));
+        assert_eq!(users[2].age, arel::ActiveValue::Unchanged(30.into()));
+
+        let names: Vec<String> = User::query().where_starts_with("name", "bulk-").order_asc("id").pluck("name").await?;
+        assert_eq!(names, vec!["bulk-1", "bulk-2", "bulk-3"]);
+        assert!(User::insert_all(vec![]).await?.is_empty());
+        Ok(())
+    }
+    async fn test_upsert() -> anyhow::Result<()> {
+        use arel::statements::OnConflict;
+        let mut user = User { id: Set(1), name: Set("upserted"), ..Default::default() };
+        assert!(user.upsert(OnConflict::columns(vec!["id"]).update_excluded(vec!["name"])).await?);
+        assert_eq!(user.name, arel::ActiveValue::Unchanged("upserted".into()));
+        assert_eq!(user.r#type, arel::ActiveValue::Unchanged(Type::Admin));
+
+        let mut user = User { id: Set(1), name: Set("skipped"), ..Default::default() };
+        assert!(!user.upsert(OnConflict::columns(vec!["id"]).do_nothing()).await?);
+        let name: Option<String> = User::query().r#where("id", 1).pick("name").await?;
+        assert_eq!(name.as_deref(), Some("upserted"));
+        assert!(user.upsert(OnConflict::any().update_excluded(vec!["name"])).await.is_err());
+
+        let models = vec![
+            User { id: Set(2), name: Set("n2"), age: Set(1), ..Default::default() },
+            User { id: Set(500), name: Set("n500"), age: Set(1), ..Default::default() },
+        ];
+        let on_conflict = OnConflict::columns(vec!["id"]).update_excluded(vec!["name"]).set("age", arel::Expr::raw(r#""users"."age" + 1"#));
+        let users = User::upsert_all(models, on_conflict).await?;
+        assert_eq!(users.len(), 2);
+        let rows: Vec<(String, Option<i32>)> = User::query().select(vec!["name", "age"]).r#where("id", vec![2, 500]).order_asc("id").fetch_all_as().await?;
+        assert_eq!(rows, vec![("n2".to_string(), None), ("n500".to_string(), Some(1))]);
+        Ok(())
+    }
 }